@@ -0,0 +1,122 @@
+// The MIT License (MIT)
+//
+// Copyright (c) 2013 Jeremy Letang (letang.jeremy@gmail.com)
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+//! One-call helpers to read and write whole sound files.
+
+use std::path::Path;
+
+use formats::Format;
+use {ErrorKind, Metadata, Sample, SndFile, SndFileError, SndFileResult, SndInfo, SndReader};
+
+/// Number of frames read at once by read_file.
+const CHUNK_FRAMES : usize = 4096;
+
+/// The whole content of a sound file
+#[derive(Clone, PartialEq, Debug)]
+pub struct AudioBuffer<T : Sample> {
+    /// The sample rate, channels and format of the file, with the number of
+    /// frames actually read
+    pub info : SndInfo,
+    /// The tags of the file
+    pub metadata : Metadata,
+    /// The interleaved samples
+    pub samples : Vec<T>
+}
+
+impl<T : Sample> AudioBuffer<T> {
+    /// The number of whole frames held by the buffer.
+    pub fn frames(&self) -> usize {
+        self.samples.len() / self.info.channels.max(1) as usize
+    }
+}
+
+/**
+ * Read a whole sound file.
+ *
+ * The samples are preallocated from the number of frames in the header, up
+ * to a fixed bound, but read until the end of the file, so files whose
+ * number of frames is unknown or wrong are read entirely. The frames of the
+ * returned SndInfo are the frames actually read.
+ *
+ * # Argument
+ * * `path` - The path of the file to read
+ *
+ * Return Ok() containing the content of the file on success, the error
+ * otherwise.
+ */
+pub fn read_file<T : Sample, P : AsRef<Path>>(path : P) -> SndFileResult<AudioBuffer<T>> {
+    let mut file = SndReader::new(path)?;
+    let metadata = file.metadata();
+    let channels = file.channels();
+    let mut samples = Vec::with_capacity(file.samples_hint());
+    let mut chunks = file.chunks::<T>(CHUNK_FRAMES);
+    while let Some(chunk) = chunks.next_chunk()? {
+        samples.extend_from_slice(chunk);
+    }
+    let mut info = file.get_sndinfo();
+    info.frames = (samples.len() / channels) as i64;
+    file.close()?;
+    Ok(AudioBuffer {
+        info,
+        metadata,
+        samples
+    })
+}
+
+/**
+ * Write a whole sound file.
+ *
+ * The tags the major format cannot hold, as listed by
+ * MajorFormat::supported_strings, are left out.
+ *
+ * # Arguments
+ * * `path` - The path of the file to create
+ * * `buffer` - The sample rate, channels, tags and samples to write, the
+ *   format of its SndInfo being ignored
+ * * `format` - The format of the file
+ *
+ * Return () on success, SystemError if fewer frames than the buffer holds
+ * were written, an other error otherwise.
+ */
+pub fn write_file<T : Sample, P : AsRef<Path>>(path : P,
+                                               buffer : &AudioBuffer<T>,
+                                               format : Format) -> SndFileResult<()> {
+    let supported = format.major().supported_strings();
+    let mut metadata = Metadata::default();
+    for (string_type, string) in buffer.metadata.iter() {
+        if supported.contains(&string_type) {
+            metadata.set(string_type, Some(string.to_string()));
+        }
+    }
+    let path = path.as_ref();
+    let mut file = SndFile::create(path)
+        .format(format)
+        .samplerate(buffer.info.samplerate)
+        .channels(buffer.info.channels)
+        .metadata(&metadata)
+        .open()?;
+    if file.write_frames(&buffer.samples)? != buffer.frames() {
+        return Err(SndFileError::new(ErrorKind::SystemError)
+                   .during("write_frames")
+                   .on(path.display()));
+    }
+    file.close()
+}
@@ -0,0 +1,334 @@
+// The MIT License (MIT)
+//
+// Copyright (c) 2013 Jeremy Letang (letang.jeremy@gmail.com)
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+//! Broadcast Wave Format (BWF) `bext` chunk.
+
+use std::mem;
+use libc::c_char;
+
+use ffi;
+use mode::{Mode, Writable};
+use {ErrorKind, SndFile, SndFileError, SndFileResult};
+
+/// The content of the `bext` chunk of a Broadcast Wave file
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct BroadcastInfo {
+    /// Description of the sound, up to 256 characters
+    pub description : String,
+    /// Name of the originator, up to 32 characters
+    pub originator : String,
+    /// Reference of the originator, up to 32 characters
+    pub originator_reference : String,
+    /// Date of creation, as yyyy-mm-dd
+    pub origination_date : String,
+    /// Time of creation, as hh:mm:ss
+    pub origination_time : String,
+    /// Position of the first sample since midnight, in samples
+    pub time_reference : u64,
+    /// Version of the BWF specification
+    pub version : i16,
+    /// SMPTE UMID of the sound
+    pub umid : [u8; 64],
+    /// Integrated loudness, in LUFS multiplied by 100
+    pub loudness_value : i16,
+    /// Loudness range, in LU multiplied by 100
+    pub loudness_range : i16,
+    /// Maximum true peak level, in dBTP multiplied by 100
+    pub max_true_peak_level : i16,
+    /// Maximum momentary loudness, in LUFS multiplied by 100
+    pub max_momentary_loudness : i16,
+    /// Maximum short-term loudness, in LUFS multiplied by 100
+    pub max_shortterm_loudness : i16,
+    /// History of the coding of the sound, one process per line
+    pub coding_history : String
+}
+
+impl Default for BroadcastInfo {
+    fn default() -> BroadcastInfo {
+        BroadcastInfo {
+            description : String::new(),
+            originator : String::new(),
+            originator_reference : String::new(),
+            origination_date : String::new(),
+            origination_time : String::new(),
+            time_reference : 0,
+            version : 0,
+            umid : [0; 64],
+            loudness_value : 0,
+            loudness_range : 0,
+            max_true_peak_level : 0,
+            max_momentary_loudness : 0,
+            max_shortterm_loudness : 0,
+            coding_history : String::new()
+        }
+    }
+}
+
+/// Room to leave in the coding history for libsndfile, which may end it with
+/// a line break before appending a line formatted in a 256 bytes buffer.
+const APPENDED_HISTORY_SIZE : usize = 2 + 256;
+
+/// Read a string from a fixed size field, which is NUL terminated only if
+/// shorter than the field.
+fn from_field(field : &[c_char]) -> String {
+    let bytes : Vec<u8> = field.iter()
+        .map(|&c| c as u8)
+        .take_while(|&b| b != 0)
+        .collect();
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+/// Copy a string to a fixed size field, returning false if it does not fit
+/// or contains a NUL byte.
+fn to_field(field : &mut [c_char], string : &str) -> bool {
+    if string.len() > field.len() || string.bytes().any(|b| b == 0) {
+        return false;
+    }
+    for (c, b) in field.iter_mut().zip(string.bytes()) {
+        *c = b as c_char;
+    }
+    true
+}
+
+/// The length of the coding history once libsndfile has turned every line
+/// break into CR LF.
+fn crlf_len(history : &str) -> usize {
+    let bytes = history.as_bytes();
+    let mut len = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        i += match (bytes[i], bytes.get(i + 1)) {
+            (b'\r', Some(&b'\n')) | (b'\n', Some(&b'\r')) => 2,
+            (b'\r', _) | (b'\n', _) => 1,
+            _ => {
+                len += 1;
+                i += 1;
+                continue;
+            }
+        };
+        len += 2;
+    }
+    len
+}
+
+impl BroadcastInfo {
+    fn from_ffi(info : &ffi::BroadcastInfo) -> BroadcastInfo {
+        let mut umid = [0; 64];
+        for (b, &c) in umid.iter_mut().zip(info.umid.iter()) {
+            *b = c as u8;
+        }
+        let history_size = (info.coding_history_size as usize).min(info.coding_history.len());
+        BroadcastInfo {
+            description : from_field(&info.description),
+            originator : from_field(&info.originator),
+            originator_reference : from_field(&info.originator_reference),
+            origination_date : from_field(&info.origination_date),
+            origination_time : from_field(&info.origination_time),
+            time_reference : (info.time_reference_high as u64) << 32
+                | info.time_reference_low as u64,
+            version : info.version,
+            umid,
+            loudness_value : info.loudness_value,
+            loudness_range : info.loudness_range,
+            max_true_peak_level : info.max_true_peak_level,
+            max_momentary_loudness : info.max_momentary_loudness,
+            max_shortterm_loudness : info.max_shortterm_loudness,
+            coding_history : from_field(&info.coding_history[..history_size])
+        }
+    }
+
+    /// Fill the libsndfile structure, returning false if a string does not
+    /// fit in its field or the coding history leaves no room for the line
+    /// appended by libsndfile.
+    fn to_ffi(&self, info : &mut ffi::BroadcastInfo) -> bool {
+        for (c, &b) in info.umid.iter_mut().zip(self.umid.iter()) {
+            *c = b as c_char;
+        }
+        info.time_reference_low = self.time_reference as u32;
+        info.time_reference_high = (self.time_reference >> 32) as u32;
+        info.version = self.version;
+        info.loudness_value = self.loudness_value;
+        info.loudness_range = self.loudness_range;
+        info.max_true_peak_level = self.max_true_peak_level;
+        info.max_momentary_loudness = self.max_momentary_loudness;
+        info.max_shortterm_loudness = self.max_shortterm_loudness;
+        info.coding_history_size = self.coding_history.len() as u32;
+        to_field(&mut info.description, &self.description)
+            && to_field(&mut info.originator, &self.originator)
+            && to_field(&mut info.originator_reference, &self.originator_reference)
+            && to_field(&mut info.origination_date, &self.origination_date)
+            && to_field(&mut info.origination_time, &self.origination_time)
+            && crlf_len(&self.coding_history) + APPENDED_HISTORY_SIZE <= info.coding_history.len()
+            && to_field(&mut info.coding_history, &self.coding_history)
+    }
+
+    /// The number of bytes of the libsndfile structure in use, which
+    /// libsndfile requires to be less than the size of the structure.
+    fn ffi_size(&self) -> usize {
+        mem::offset_of!(ffi::BroadcastInfo, coding_history) + self.coding_history.len()
+    }
+}
+
+fn zeroed_info() -> Box<ffi::BroadcastInfo> {
+    Box::new(unsafe { mem::zeroed() })
+}
+
+impl<M : Mode> SndFile<M> {
+    /**
+     * Read the `bext` chunk of a Broadcast Wave file.
+     *
+     * Return Some(BroadcastInfo) if the file has a `bext` chunk, None
+     * otherwise.
+     */
+    pub fn broadcast_info(&self) -> Option<BroadcastInfo> {
+        let mut info = zeroed_info();
+        match self.command(ffi::SFC_GET_BROADCAST_INFO, &mut *info) {
+            ffi::SF_TRUE => Some(BroadcastInfo::from_ffi(&info)),
+            _ => None
+        }
+    }
+}
+
+impl<M : Writable> SndFile<M> {
+    /**
+     * Write the `bext` chunk of a Broadcast Wave file.
+     *
+     * This must be done before writing any audio data. libsndfile appends
+     * its own line to the coding history, which must leave room for it, and
+     * turns its line breaks into CR LF.
+     *
+     * # Argument
+     * * `broadcast_info` - The content of the chunk
+     *
+     * Return () on success, InvalidString if a string does not fit in its
+     * field, the error of libsndfile otherwise, or UnsupportedCommand if the
+     * format has no `bext` chunk.
+     */
+    pub fn set_broadcast_info(&mut self, broadcast_info : &BroadcastInfo) -> SndFileResult<()> {
+        let mut info = zeroed_info();
+        if !broadcast_info.to_ffi(&mut info) {
+            return Err(SndFileError::new(ErrorKind::InvalidString)
+                .during("SFC_SET_BROADCAST_INFO")
+                .on(&self.origin));
+        }
+        if self.command_sized(ffi::SFC_SET_BROADCAST_INFO,
+                              &mut *info,
+                              broadcast_info.ffi_size()) == ffi::SF_TRUE {
+            Ok(())
+        } else {
+            Err(self.command_error(ErrorKind::UnsupportedCommand, "SFC_SET_BROADCAST_INFO"))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs;
+
+    use super::*;
+    use formats::{Format, MajorFormat, SubtypeFormat};
+    use {SndReader, SndWriter};
+
+    fn sample_info() -> BroadcastInfo {
+        BroadcastInfo {
+            description : "A test sound".to_string(),
+            originator : "sndfile".to_string(),
+            originator_reference : "REF0001".to_string(),
+            origination_date : "2024-01-02".to_string(),
+            origination_time : "03:04:05".to_string(),
+            time_reference : 0x1_0000_0002,
+            version : 2,
+            umid : [7; 64],
+            loudness_value : -2300,
+            loudness_range : 500,
+            max_true_peak_level : -100,
+            max_momentary_loudness : -1800,
+            max_shortterm_loudness : -2000,
+            coding_history : "A=PCM,F=48000,W=24,M=mono\r\n".to_string()
+        }
+    }
+
+    #[test]
+    fn ffi_round_trip() {
+        let info = sample_info();
+        let mut raw = zeroed_info();
+        assert!(info.to_ffi(&mut raw));
+        assert_eq!(raw.coding_history_size as usize, info.coding_history.len());
+        assert_eq!(BroadcastInfo::from_ffi(&raw), info);
+    }
+
+    #[test]
+    fn to_ffi_rejects_long_strings() {
+        let mut raw = zeroed_info();
+        let mut info = sample_info();
+        info.originator = "x".repeat(33);
+        assert!(!info.to_ffi(&mut raw));
+
+        let mut info = sample_info();
+        info.description = "nul\0byte".to_string();
+        assert!(!info.to_ffi(&mut raw));
+
+        let max = ffi::BROADCAST_CODING_HISTORY_SIZE - APPENDED_HISTORY_SIZE;
+        let mut info = sample_info();
+        info.coding_history = "x".repeat(max);
+        assert!(info.to_ffi(&mut raw));
+        info.coding_history = "x".repeat(max + 1);
+        assert!(!info.to_ffi(&mut raw));
+        // Lone line feeds are written as CR LF.
+        info.coding_history = "\n".repeat(max / 2 + 1);
+        assert!(!info.to_ffi(&mut raw));
+    }
+
+    #[test]
+    fn crlf_length() {
+        assert_eq!(crlf_len(""), 0);
+        assert_eq!(crlf_len("ab"), 2);
+        assert_eq!(crlf_len("a\r\nb"), 4);
+        assert_eq!(crlf_len("a\nb"), 4);
+        assert_eq!(crlf_len("a\n\rb"), 4);
+        assert_eq!(crlf_len("\r\r"), 4);
+    }
+
+    #[test]
+    fn write_then_read() {
+        let path = env::temp_dir().join(format!("sndfile-bext-{}.wav", ::std::process::id()));
+        let info = sample_info();
+        {
+            let mut writer : SndWriter = SndFile::create(&path)
+                .format(Format::new(MajorFormat::Wav, SubtypeFormat::Pcm16))
+                .samplerate(48000)
+                .channels(1)
+                .open()
+                .unwrap();
+            writer.set_broadcast_info(&info).unwrap();
+            writer.write_i16(&[0, 1, 2, 3]).unwrap();
+            writer.close().unwrap();
+        }
+        let read = SndReader::new(&path).unwrap().broadcast_info();
+        fs::remove_file(&path).unwrap();
+
+        let read = read.unwrap();
+        assert!(read.coding_history.starts_with(&info.coding_history));
+        assert!(read.coding_history.len() > info.coding_history.len());
+        assert_eq!(BroadcastInfo { coding_history : info.coding_history.clone(), ..read }, info);
+    }
+}
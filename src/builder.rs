@@ -0,0 +1,259 @@
+// The MIT License (MIT)
+//
+// Copyright (c) 2013 Jeremy Letang (letang.jeremy@gmail.com)
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+//! Builder used to create new sound files.
+
+use std::ffi::CString;
+use std::path::{Path, PathBuf};
+
+use encoder;
+use ffi;
+use {BitrateMode, Endian, Format, MajorFormat, Metadata, SubtypeFormat};
+use {ErrorKind, SndFile, SndFileError, SndFileResult, SndInfo, SndWriter, StringSoundType};
+
+/// Builder returned by SndFile::create, to set the format of a new sound file
+/// before opening it for writing.
+///
+/// By default the file is a stereo 16 bit PCM WAV file at 44100 Hz.
+#[derive(Clone, Debug)]
+pub struct WriterBuilder {
+    path : PathBuf,
+    format : Format,
+    endian : Option<Endian>,
+    samplerate : i32,
+    channels : i32,
+    strings : Vec<(StringSoundType, String)>,
+    vbr_quality : Option<f64>,
+    compression_level : Option<f64>,
+    bitrate_mode : Option<BitrateMode>,
+    norm_float : Option<bool>,
+    norm_double : Option<bool>,
+    scale_int_float_write : Option<bool>,
+    clipping : Option<bool>,
+    add_peak_chunk : Option<bool>
+}
+
+impl WriterBuilder {
+    /// Create a builder for a file at the given path.
+    pub fn new<P : AsRef<Path>>(path : P) -> WriterBuilder {
+        WriterBuilder {
+            path : path.as_ref().to_path_buf(),
+            format : Format::new(MajorFormat::Wav, SubtypeFormat::Pcm16),
+            endian : None,
+            samplerate : 44100,
+            channels : 2,
+            strings : Vec::new(),
+            vbr_quality : None,
+            compression_level : None,
+            bitrate_mode : None,
+            norm_float : None,
+            norm_double : None,
+            scale_int_float_write : None,
+            clipping : None,
+            add_peak_chunk : None
+        }
+    }
+
+    /// Set the format of the file.
+    pub fn format(mut self, format : Format) -> WriterBuilder {
+        self.format = format;
+        self
+    }
+
+    /// Set the sample rate of the file.
+    pub fn samplerate(mut self, samplerate : i32) -> WriterBuilder {
+        self.samplerate = samplerate;
+        self
+    }
+
+    /// Set the number of channels of the file.
+    pub fn channels(mut self, channels : i32) -> WriterBuilder {
+        self.channels = channels;
+        self
+    }
+
+    /// Set the endian-ness of the file, overriding the one of the format.
+    pub fn endian(mut self, endian : Endian) -> WriterBuilder {
+        self.endian = Some(endian);
+        self
+    }
+
+    /// Add a tag written to the file when it is opened.
+    pub fn string(mut self, string_type : StringSoundType, string : &str) -> WriterBuilder {
+        self.strings.push((string_type, string.to_string()));
+        self
+    }
+
+    /// Add all the tags set in a Metadata, written to the file when it is
+    /// opened.
+    pub fn metadata(mut self, metadata : &Metadata) -> WriterBuilder {
+        for (string_type, string) in metadata.iter() {
+            self.strings.push((string_type, string.to_string()));
+        }
+        self
+    }
+
+    /// Set the encoding quality of variable bit rate formats, from 0.0
+    /// (lowest) to 1.0 (highest).
+    pub fn vbr_quality(mut self, quality : f64) -> WriterBuilder {
+        self.vbr_quality = Some(quality);
+        self
+    }
+
+    /// Set the compression level of compressed formats, from 0.0 (fastest)
+    /// to 1.0 (smallest).
+    pub fn compression_level(mut self, level : f64) -> WriterBuilder {
+        self.compression_level = Some(level);
+        self
+    }
+
+    /// Set the bit rate mode of lossy formats.
+    pub fn bitrate_mode(mut self, mode : BitrateMode) -> WriterBuilder {
+        self.bitrate_mode = Some(mode);
+        self
+    }
+
+    /// Set whether the f32 samples written are normalized to [-1.0, 1.0].
+    pub fn norm_float(mut self, norm : bool) -> WriterBuilder {
+        self.norm_float = Some(norm);
+        self
+    }
+
+    /// Set whether the f64 samples written are normalized to [-1.0, 1.0].
+    pub fn norm_double(mut self, norm : bool) -> WriterBuilder {
+        self.norm_double = Some(norm);
+        self
+    }
+
+    /// Set whether integers written to float data are scaled down from the
+    /// full range of their type.
+    pub fn scale_int_float_write(mut self, scale : bool) -> WriterBuilder {
+        self.scale_int_float_write = Some(scale);
+        self
+    }
+
+    /// Set whether float values out of range are clipped instead of wrapped
+    /// around when written as integers.
+    pub fn clipping(mut self, clipping : bool) -> WriterBuilder {
+        self.clipping = Some(clipping);
+        self
+    }
+
+    /// Set whether a PEAK chunk is added to WAV, AIFF, CAF and RF64 float
+    /// files, which libsndfile does by default.
+    pub fn add_peak_chunk(mut self, add : bool) -> WriterBuilder {
+        self.add_peak_chunk = Some(add);
+        self
+    }
+
+    /// The SndInfo the file will be opened with.
+    pub fn sndinfo(&self) -> SndInfo {
+        let format = match self.endian {
+            Some(endian) => self.format.with_endian(endian),
+            None => self.format
+        };
+        SndInfo {
+            samplerate : self.samplerate,
+            channels : self.channels,
+            format : format.to_raw(),
+            ..SndInfo::default()
+        }
+    }
+
+    /**
+     * Check the format, create the file and apply the tags, encoder and
+     * conversion options.
+     *
+     * Return Ok() containing the SndWriter on success, InvalidFormat if
+     * libsndfile rejects the format, UnsupportedEncoding if the linked
+     * libsndfile was built without its codec, UnsupportedCommand or
+     * InvalidValue if an encoder or PEAK chunk option does not apply to the
+     * format, InvalidString if a tag contains a NUL byte, an other error
+     * otherwise. The file is not created when the format, one of these
+     * options or a tag is rejected.
+     */
+    pub fn open(self) -> SndFileResult<SndWriter> {
+        let info = self.sndinfo();
+        if !SndFile::check_format(&info) {
+            return Err(SndFileError::new(ErrorKind::InvalidFormat)
+                       .during("create")
+                       .on(self.path.display()));
+        }
+        // sf_format_check does not know which codecs libsndfile was built with.
+        let format = info.format();
+        if !format.major().is_supported() || !format.subtype().is_supported() {
+            return Err(SndFileError::new(ErrorKind::UnsupportedEncoding)
+                       .during("create")
+                       .on(self.path.display()));
+        }
+        let options = [
+            (self.vbr_quality, ffi::SFC_SET_VBR_ENCODING_QUALITY, "SFC_SET_VBR_ENCODING_QUALITY"),
+            (self.compression_level, ffi::SFC_SET_COMPRESSION_LEVEL, "SFC_SET_COMPRESSION_LEVEL"),
+            (self.bitrate_mode.map(|_| 0.0), ffi::SFC_SET_BITRATE_MODE, "SFC_SET_BITRATE_MODE")
+        ];
+        for &(value, cmd, operation) in &options {
+            if let Some(value) = value {
+                encoder::check_option(format, cmd, value).map_err(|kind| {
+                    SndFileError::new(kind).during(operation).on(self.path.display())
+                })?;
+            }
+        }
+        if self.add_peak_chunk == Some(true) && !format.supports_peak_chunk() {
+            return Err(SndFileError::new(ErrorKind::UnsupportedCommand)
+                       .during("SFC_SET_ADD_PEAK_CHUNK")
+                       .on(self.path.display()));
+        }
+        if self.strings.iter().any(|(_, string)| CString::new(string.as_str()).is_err()) {
+            return Err(SndFileError::new(ErrorKind::InvalidString)
+                       .during("set_string")
+                       .on(self.path.display()));
+        }
+        let mut file = SndWriter::open(&self.path, info)?;
+        for &(string_type, ref string) in &self.strings {
+            file.set_string(string_type, string)?;
+        }
+        if let Some(quality) = self.vbr_quality {
+            file.set_vbr_quality(quality)?;
+        }
+        if let Some(level) = self.compression_level {
+            file.set_compression_level(level)?;
+        }
+        if let Some(mode) = self.bitrate_mode {
+            file.set_bitrate_mode(mode)?;
+        }
+        if let Some(norm) = self.norm_float {
+            file.set_norm_float(norm);
+        }
+        if let Some(norm) = self.norm_double {
+            file.set_norm_double(norm);
+        }
+        if let Some(scale) = self.scale_int_float_write {
+            file.set_scale_int_float_write(scale);
+        }
+        if let Some(clipping) = self.clipping {
+            file.set_clipping(clipping);
+        }
+        if let Some(add) = self.add_peak_chunk {
+            file.set_add_peak_chunk(add)?;
+        }
+        Ok(file)
+    }
+}
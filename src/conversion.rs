@@ -0,0 +1,97 @@
+// The MIT License (MIT)
+//
+// Copyright (c) 2013 Jeremy Letang (letang.jeremy@gmail.com)
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+//! Control of the conversion between float and integer samples.
+//!
+//! By default libsndfile reads and writes floats normalized to [-1.0, 1.0]
+//! and wraps around float values out of range when writing integer data.
+
+use ffi;
+use mode::{Mode, Readable, Writable};
+use SndFile;
+
+fn to_bool(value : i32) -> bool {
+    value != ffi::SF_FALSE
+}
+
+fn from_bool(value : bool) -> i32 {
+    if value { ffi::SF_TRUE } else { ffi::SF_FALSE }
+}
+
+impl<M : Mode> SndFile<M> {
+    /// Whether f32 samples are normalized to [-1.0, 1.0], true by default.
+    pub fn norm_float(&self) -> bool {
+        to_bool(self.command_value(ffi::SFC_GET_NORM_FLOAT, 0))
+    }
+
+    /// Set whether f32 samples are normalized to [-1.0, 1.0], and return the
+    /// previous setting.
+    pub fn set_norm_float(&mut self, norm : bool) -> bool {
+        to_bool(self.command_value(ffi::SFC_SET_NORM_FLOAT, from_bool(norm)))
+    }
+
+    /// Whether f64 samples are normalized to [-1.0, 1.0], true by default.
+    pub fn norm_double(&self) -> bool {
+        to_bool(self.command_value(ffi::SFC_GET_NORM_DOUBLE, 0))
+    }
+
+    /// Set whether f64 samples are normalized to [-1.0, 1.0], and return the
+    /// previous setting.
+    pub fn set_norm_double(&mut self, norm : bool) -> bool {
+        to_bool(self.command_value(ffi::SFC_SET_NORM_DOUBLE, from_bool(norm)))
+    }
+
+    /// Whether float values out of range are clipped instead of wrapped
+    /// around when converted to integers, false by default.
+    pub fn clipping(&self) -> bool {
+        to_bool(self.command_value(ffi::SFC_GET_CLIPPING, 0))
+    }
+
+    /// Set whether float values out of range are clipped instead of wrapped
+    /// around when converted to integers.
+    pub fn set_clipping(&mut self, clipping : bool) {
+        self.command_value(ffi::SFC_SET_CLIPPING, from_bool(clipping));
+    }
+}
+
+impl<M : Readable> SndFile<M> {
+    /**
+     * Set whether float data is scaled to the full range of the integer type
+     * when read as integers, and return the previous setting.
+     *
+     * libsndfile has no getter for this setting, which is false by default.
+     */
+    pub fn set_scale_float_int_read(&mut self, scale : bool) -> bool {
+        to_bool(self.command_value(ffi::SFC_SET_SCALE_FLOAT_INT_READ, from_bool(scale)))
+    }
+}
+
+impl<M : Writable> SndFile<M> {
+    /**
+     * Set whether integers written to float data are scaled down from the
+     * full range of their type, and return the previous setting.
+     *
+     * libsndfile has no getter for this setting, which is false by default.
+     */
+    pub fn set_scale_int_float_write(&mut self, scale : bool) -> bool {
+        to_bool(self.command_value(ffi::SFC_SET_SCALE_INT_FLOAT_WRITE, from_bool(scale)))
+    }
+}
@@ -0,0 +1,217 @@
+// The MIT License (MIT)
+//
+// Copyright (c) 2013 Jeremy Letang (letang.jeremy@gmail.com)
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+//! Cue points, the markers of WAV and AIFF files.
+
+use std::mem;
+use std::slice;
+use libc::c_char;
+
+use ffi;
+use mode::{Mode, Writable};
+use {ErrorKind, SndFile, SndFileError, SndFileResult};
+
+/// A cue point, as stored in the `cue ` chunk of WAV files or the `MARK`
+/// chunk of AIFF files
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct CuePoint {
+    /// Unique identifier of the cue point
+    pub id : i32,
+    /// Position of the cue point in the play order
+    pub position : u32,
+    /// Identifier of the chunk containing the cue point, `data` by default
+    pub fcc_chunk : [u8; 4],
+    /// Offset of the chunk containing the cue point, 0 for the data chunk
+    pub chunk_start : i32,
+    /// Offset of the block containing the cue point, 0 for uncompressed data
+    pub block_start : i32,
+    /// Frame of the cue point, relative to the block start
+    pub sample_offset : u32,
+    /// Name of the cue point, up to 255 bytes
+    pub name : String
+}
+
+impl Default for CuePoint {
+    fn default() -> CuePoint {
+        CuePoint {
+            id : 0,
+            position : 0,
+            fcc_chunk : *b"data",
+            chunk_start : 0,
+            block_start : 0,
+            sample_offset : 0,
+            name : String::new()
+        }
+    }
+}
+
+impl CuePoint {
+    fn from_ffi(point : &ffi::CuePoint) -> CuePoint {
+        let name : Vec<u8> = point.name.iter()
+            .map(|&c| c as u8)
+            .take_while(|&b| b != 0)
+            .collect();
+        CuePoint {
+            id : point.indx,
+            position : point.position,
+            // libsndfile builds the identifiers so that their bytes in memory
+            // are the characters of the identifier.
+            fcc_chunk : point.fcc_chunk.to_ne_bytes(),
+            chunk_start : point.chunk_start,
+            block_start : point.block_start,
+            sample_offset : point.sample_offset,
+            name : String::from_utf8_lossy(&name).into_owned()
+        }
+    }
+
+    /// Fill the libsndfile structure, returning false if the name does not
+    /// fit in its NUL terminated field.
+    fn to_ffi(&self, point : &mut ffi::CuePoint) -> bool {
+        if self.name.len() >= point.name.len() || self.name.bytes().any(|b| b == 0) {
+            return false;
+        }
+        point.indx = self.id;
+        point.position = self.position;
+        point.fcc_chunk = i32::from_ne_bytes(self.fcc_chunk);
+        point.chunk_start = self.chunk_start;
+        point.block_start = self.block_start;
+        point.sample_offset = self.sample_offset;
+        for (c, b) in point.name.iter_mut().zip(self.name.bytes()) {
+            *c = b as c_char;
+        }
+        true
+    }
+}
+
+/// Zeroed buffer holding an SF_CUES structure with room for `count` points,
+/// made of u32 to get the alignment of the structure.
+fn cues_buffer(count : usize) -> Vec<u32> {
+    let words = 1 + count * mem::size_of::<ffi::CuePoint>() / mem::size_of::<u32>();
+    vec![0; words]
+}
+
+/// The cue points following the count in an SF_CUES buffer.
+fn cue_points(buffer : &mut [u32], count : usize) -> &mut [ffi::CuePoint] {
+    unsafe {
+        slice::from_raw_parts_mut(buffer.as_mut_ptr().offset(1) as *mut ffi::CuePoint, count)
+    }
+}
+
+impl<M : Mode> SndFile<M> {
+    /**
+     * Read the cue points of the file.
+     *
+     * Return Some(Vec<CuePoint>) if the file has cue points, None otherwise.
+     */
+    pub fn cues(&self) -> Option<Vec<CuePoint>> {
+        let mut count : u32 = 0;
+        if self.command(ffi::SFC_GET_CUE_COUNT, &mut count) != ffi::SF_TRUE {
+            return None;
+        }
+        let mut buffer = cues_buffer(count as usize);
+        if self.command_buffer(ffi::SFC_GET_CUE, &mut buffer) != ffi::SF_TRUE {
+            return None;
+        }
+        let count = buffer[0].min(count) as usize;
+        Some(cue_points(&mut buffer, count).iter().map(CuePoint::from_ffi).collect())
+    }
+}
+
+impl<M : Writable> SndFile<M> {
+    /**
+     * Set the cue points written to the file.
+     *
+     * This must be done before writing any audio data.
+     *
+     * # Argument
+     * * `cues` - The cue points of the file
+     *
+     * Return () on success, InvalidString if a name is too long or contains
+     * a NUL byte, the error of libsndfile otherwise, or UnsupportedCommand
+     * if the format has no cue points.
+     */
+    pub fn set_cues(&mut self, cues : &[CuePoint]) -> SndFileResult<()> {
+        let mut buffer = cues_buffer(cues.len());
+        buffer[0] = cues.len() as u32;
+        let valid = cues.iter()
+            .zip(cue_points(&mut buffer, cues.len()).iter_mut())
+            .all(|(cue, point)| cue.to_ffi(point));
+        if !valid {
+            return Err(SndFileError::new(ErrorKind::InvalidString)
+                .during("SFC_SET_CUE")
+                .on(&self.origin));
+        }
+        if self.command_buffer(ffi::SFC_SET_CUE, &mut buffer) == ffi::SF_TRUE {
+            Ok(())
+        } else {
+            Err(self.command_error(ErrorKind::UnsupportedCommand, "SFC_SET_CUE"))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ffi_round_trip() {
+        let cue = CuePoint {
+            id : 3,
+            position : 1,
+            fcc_chunk : *b"slnt",
+            chunk_start : 12,
+            block_start : 34,
+            sample_offset : 5678,
+            name : "Chorus".to_string()
+        };
+        let mut point : ffi::CuePoint = unsafe { mem::zeroed() };
+        assert!(cue.to_ffi(&mut point));
+        assert_eq!(CuePoint::from_ffi(&point), cue);
+    }
+
+    #[test]
+    fn default_chunk_is_data() {
+        let mut point : ffi::CuePoint = unsafe { mem::zeroed() };
+        assert!(CuePoint::default().to_ffi(&mut point));
+        let bytes = point.fcc_chunk.to_ne_bytes();
+        assert_eq!(&bytes, b"data");
+    }
+
+    #[test]
+    fn to_ffi_rejects_invalid_names() {
+        let mut point : ffi::CuePoint = unsafe { mem::zeroed() };
+        let long = CuePoint { name : "x".repeat(256), ..CuePoint::default() };
+        assert!(!long.to_ffi(&mut point));
+        let nul = CuePoint { name : "a\0b".to_string(), ..CuePoint::default() };
+        assert!(!nul.to_ffi(&mut point));
+    }
+
+    #[test]
+    fn buffer_layout() {
+        let mut buffer = cues_buffer(2);
+        buffer[0] = 2;
+        cue_points(&mut buffer, 2)[1].sample_offset = 42;
+        let offset = 1 + (mem::size_of::<ffi::CuePoint>() + 20) / mem::size_of::<u32>();
+        assert_eq!(buffer[offset], 42);
+        assert_eq!(buffer.len() * mem::size_of::<u32>(),
+                   mem::size_of::<u32>() + 2 * mem::size_of::<ffi::CuePoint>());
+    }
+}
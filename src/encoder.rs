@@ -0,0 +1,163 @@
+// The MIT License (MIT)
+//
+// Copyright (c) 2013 Jeremy Letang (letang.jeremy@gmail.com)
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+//! Options of the encoders of compressed formats.
+
+use ffi;
+use formats::Format;
+use mode::{Mode, Writable};
+use {ErrorKind, SndFile, SndFileError, SndFileResult};
+
+/// How the bit rate of a lossy encoder varies
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum BitrateMode {
+    /// Constant bit rate
+    Constant,
+    /// Average bit rate
+    Average,
+    /// Variable bit rate
+    Variable
+}
+
+impl BitrateMode {
+    /// Get the raw libsndfile value of the bit rate mode.
+    pub fn to_raw(self) -> i32 {
+        match self {
+            BitrateMode::Constant => ffi::SF_BITRATE_MODE_CONSTANT,
+            BitrateMode::Average => ffi::SF_BITRATE_MODE_AVERAGE,
+            BitrateMode::Variable => ffi::SF_BITRATE_MODE_VARIABLE
+        }
+    }
+
+    /// Get the bit rate mode from its raw libsndfile value, None if unknown.
+    pub fn from_raw(raw : i32) -> Option<BitrateMode> {
+        match raw {
+            ffi::SF_BITRATE_MODE_CONSTANT => Some(BitrateMode::Constant),
+            ffi::SF_BITRATE_MODE_AVERAGE => Some(BitrateMode::Average),
+            ffi::SF_BITRATE_MODE_VARIABLE => Some(BitrateMode::Variable),
+            _ => None
+        }
+    }
+}
+
+/// Check that `format` has the encoder option set by `cmd`, and that its
+/// value is in range for the options taking a double.
+pub(crate) fn check_option(format : Format,
+                           cmd : ffi::SF_COMMAND,
+                           value : f64) -> Result<(), ErrorKind> {
+    let supported = match cmd {
+        ffi::SFC_SET_VBR_ENCODING_QUALITY => format.supports_vbr_quality(),
+        ffi::SFC_SET_COMPRESSION_LEVEL => format.supports_compression_level(),
+        ffi::SFC_SET_BITRATE_MODE => format.supports_bitrate_mode(),
+        _ => false
+    };
+    if !supported {
+        Err(ErrorKind::UnsupportedCommand)
+    } else if !(0.0..=1.0).contains(&value) {
+        Err(ErrorKind::InvalidValue)
+    } else {
+        Ok(())
+    }
+}
+
+impl<M : Mode> SndFile<M> {
+    /**
+     * Get the bit rate mode of the encoder or decoder, None if the format has
+     * no bit rate mode.
+     *
+     * libsndfile has no SFC_GET_CURRENT_BITRATE command, so the current bit
+     * rate of a file cannot be queried; this mode is the only bit rate
+     * information available.
+     */
+    pub fn bitrate_mode(&self) -> Option<BitrateMode> {
+        // The command returns 0, the raw constant mode, for other formats.
+        if !self.info.format().supports_bitrate_mode() {
+            return None;
+        }
+        BitrateMode::from_raw(self.command_value(ffi::SFC_GET_BITRATE_MODE, 0))
+    }
+}
+
+impl<M : Writable> SndFile<M> {
+    fn set_encoder_option<T>(&mut self,
+                             cmd : ffi::SF_COMMAND,
+                             operation : &'static str,
+                             check_value : f64,
+                             mut value : T) -> SndFileResult<()> {
+        if let Err(kind) = check_option(self.get_sndinfo().format(), cmd, check_value) {
+            return Err(SndFileError::new(kind)
+                .during(operation)
+                .on(&self.origin));
+        }
+        if self.command(cmd, &mut value) == ffi::SF_TRUE {
+            Ok(())
+        } else {
+            Err(self.command_error(ErrorKind::UnsupportedCommand, operation))
+        }
+    }
+
+    /**
+     * Set the encoding quality of a variable bit rate format, from 0.0
+     * (lowest) to 1.0 (highest).
+     *
+     * This must be done before writing any audio data.
+     *
+     * Return () on success, UnsupportedCommand if the format has no variable
+     * bit rate quality, InvalidValue if the quality is out of range.
+     */
+    pub fn set_vbr_quality(&mut self, quality : f64) -> SndFileResult<()> {
+        self.set_encoder_option(ffi::SFC_SET_VBR_ENCODING_QUALITY,
+                                "SFC_SET_VBR_ENCODING_QUALITY",
+                                quality,
+                                quality)
+    }
+
+    /**
+     * Set the compression level of a compressed format, from 0.0 (fastest)
+     * to 1.0 (smallest).
+     *
+     * This must be done before writing any audio data.
+     *
+     * Return () on success, UnsupportedCommand if the format has no
+     * compression level, InvalidValue if the level is out of range.
+     */
+    pub fn set_compression_level(&mut self, level : f64) -> SndFileResult<()> {
+        self.set_encoder_option(ffi::SFC_SET_COMPRESSION_LEVEL,
+                                "SFC_SET_COMPRESSION_LEVEL",
+                                level,
+                                level)
+    }
+
+    /**
+     * Set the bit rate mode of a lossy format.
+     *
+     * This must be done before writing any audio data.
+     *
+     * Return () on success, UnsupportedCommand if the format has no bit rate
+     * mode.
+     */
+    pub fn set_bitrate_mode(&mut self, mode : BitrateMode) -> SndFileResult<()> {
+        self.set_encoder_option(ffi::SFC_SET_BITRATE_MODE,
+                                "SFC_SET_BITRATE_MODE",
+                                0.0,
+                                mode.to_raw())
+    }
+}
@@ -0,0 +1,273 @@
+// The MIT License (MIT)
+//
+// Copyright (c) 2013 Jeremy Letang (letang.jeremy@gmail.com)
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+//! The error type returned by the API functions.
+
+use std::error::Error;
+use std::ffi::CStr;
+use std::fmt;
+use std::io;
+
+use ffi;
+
+/// Kinds of error who can be return by API functions
+#[derive(Clone, PartialEq, Eq, Debug, Copy)]
+pub enum ErrorKind {
+    /// The file format is not recognized
+    UnrecognisedFormat,
+    /// There is an internal system error
+    SystemError,
+    /// The file is malformed
+    MalformedFile,
+    /// The encoding of the file is not supported by sndfile
+    UnsupportedEncoding,
+    /// The file was not opened from a path and cannot be reopened
+    NotReopenable,
+    /// The stream behind a virtual I/O SndFile failed
+    Io(io::ErrorKind),
+    /// The format, sample rate and channels of a file to write are invalid
+    InvalidFormat,
+    /// The command is not supported by the format of the file
+    UnsupportedCommand,
+    /// The length of a buffer is not a whole number of frames, or of blocks
+    /// for raw data
+    InvalidBufferLength,
+    /// The path contains a NUL byte, or cannot be passed to libsndfile
+    InvalidPath,
+    /// A string to write to the file contains a NUL byte or is too long for
+    /// its field
+    InvalidString,
+    /// A value to write to the file is out of the range libsndfile accepts
+    InvalidValue,
+    /// The file is not seekable, as for pipes
+    NotSeekable,
+    /// Any internal error code
+    InternalError(i32)
+}
+
+impl ErrorKind {
+    /// Get the kind of a libsndfile error code, None for SF_ERR_NO_ERROR.
+    pub fn from_code(code : i32) -> Option<ErrorKind> {
+        match code {
+            ffi::SF_ERR_NO_ERROR => None,
+            ffi::SF_ERR_UNRECOGNISED_FORMAT => Some(ErrorKind::UnrecognisedFormat),
+            ffi::SF_ERR_SYSTEM => Some(ErrorKind::SystemError),
+            ffi::SF_ERR_MALFORMED_FILE => Some(ErrorKind::MalformedFile),
+            ffi::SF_ERR_UNSUPPORTED_ENCODING => Some(ErrorKind::UnsupportedEncoding),
+            _ => Some(ErrorKind::InternalError(code))
+        }
+    }
+
+    /// Get the libsndfile error code of the kind, 0 for the errors raised by
+    /// this binding.
+    pub fn code(&self) -> i32 {
+        match *self {
+            ErrorKind::UnrecognisedFormat => ffi::SF_ERR_UNRECOGNISED_FORMAT,
+            ErrorKind::SystemError => ffi::SF_ERR_SYSTEM,
+            ErrorKind::MalformedFile => ffi::SF_ERR_MALFORMED_FILE,
+            ErrorKind::UnsupportedEncoding => ffi::SF_ERR_UNSUPPORTED_ENCODING,
+            ErrorKind::InternalError(code) => code,
+            _ => ffi::SF_ERR_NO_ERROR
+        }
+    }
+
+    /// Get a generic description of the kind, as returned by libsndfile's
+    /// sf_error_number for libsndfile errors.
+    pub fn desc(&self) -> String {
+        match *self {
+            ErrorKind::NotReopenable =>
+                "The file was not opened from a path and cannot be reopened.".to_string(),
+            ErrorKind::Io(kind) => format!("Virtual I/O stream error: {:?}.", kind),
+            ErrorKind::InvalidFormat =>
+                "Invalid combination of format, sample rate and channels.".to_string(),
+            ErrorKind::UnsupportedCommand =>
+                "Command not supported by the format of the file.".to_string(),
+            ErrorKind::InvalidBufferLength =>
+                "Buffer length is not a whole number of frames or blocks.".to_string(),
+            ErrorKind::InvalidPath =>
+                "Path contains a NUL byte or cannot be passed to libsndfile.".to_string(),
+            ErrorKind::InvalidString =>
+                "String contains a NUL byte or is too long for its field.".to_string(),
+            ErrorKind::InvalidValue =>
+                "Value out of the range accepted by libsndfile.".to_string(),
+            ErrorKind::NotSeekable => "The file is not seekable.".to_string(),
+            _ => unsafe {
+                CStr::from_ptr(ffi::sf_error_number(self.code())).to_string_lossy().into_owned()
+            }
+        }
+    }
+
+    fn io_kind(&self) -> io::ErrorKind {
+        match *self {
+            ErrorKind::Io(kind) => kind,
+            ErrorKind::UnrecognisedFormat
+                | ErrorKind::MalformedFile
+                | ErrorKind::UnsupportedEncoding => io::ErrorKind::InvalidData,
+            ErrorKind::InvalidFormat
+                | ErrorKind::InvalidBufferLength
+                | ErrorKind::InvalidPath
+                | ErrorKind::InvalidString
+                | ErrorKind::InvalidValue => io::ErrorKind::InvalidInput,
+            ErrorKind::NotReopenable
+                | ErrorKind::UnsupportedCommand
+                | ErrorKind::NotSeekable => io::ErrorKind::Unsupported,
+            _ => io::ErrorKind::Other
+        }
+    }
+}
+
+/// Error returned by API functions.
+///
+/// Besides its kind, the error records the operation which failed, the path
+/// or stream it failed on, and the detailed message of libsndfile.
+#[derive(Debug)]
+pub struct SndFileError {
+    kind : ErrorKind,
+    operation : Option<&'static str>,
+    target : Option<String>,
+    message : String,
+    source : Option<io::Error>
+}
+
+impl SndFileError {
+    /// Create an error of the given kind with its generic description as
+    /// message.
+    pub fn new(kind : ErrorKind) -> SndFileError {
+        SndFileError {
+            kind,
+            operation : None,
+            target : None,
+            message : kind.desc(),
+            source : None
+        }
+    }
+
+    /// Create an error from a libsndfile handle, with the message returned by
+    /// sf_strerror. A null handle gives the last error of sf_open and
+    /// friends.
+    ///
+    /// Return None if the handle has no error.
+    pub(crate) fn from_handle(handle : *mut ffi::SNDFILE) -> Option<SndFileError> {
+        let kind = ErrorKind::from_code(unsafe { ffi::sf_error(handle) })?;
+        let message = unsafe {
+            CStr::from_ptr(ffi::sf_strerror(handle)).to_string_lossy().into_owned()
+        };
+        Some(SndFileError {
+            message,
+            ..SndFileError::new(kind)
+        })
+    }
+
+    /// Create an error from a code returned by libsndfile.
+    ///
+    /// Return None if the code is SF_ERR_NO_ERROR.
+    pub(crate) fn from_code(code : i32) -> Option<SndFileError> {
+        ErrorKind::from_code(code).map(SndFileError::new)
+    }
+
+    /// Set the operation which failed.
+    pub(crate) fn during(mut self, operation : &'static str) -> SndFileError {
+        self.operation = Some(operation);
+        self
+    }
+
+    /// Set the path or stream the operation failed on.
+    pub(crate) fn on<T : fmt::Display>(mut self, target : T) -> SndFileError {
+        self.target = Some(target.to_string());
+        self
+    }
+
+    /// The kind of the error.
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    /// The libsndfile error code, 0 if the error was raised by this binding.
+    pub fn code(&self) -> i32 {
+        self.kind.code()
+    }
+
+    /// The operation which failed, if known.
+    pub fn operation(&self) -> Option<&str> {
+        self.operation
+    }
+
+    /// The path or stream the operation failed on, if known.
+    pub fn target(&self) -> Option<&str> {
+        self.target.as_ref().map(|target| &target[..])
+    }
+
+    /// The detailed message of the error, as returned by sf_strerror for
+    /// libsndfile errors.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Get a string representation of the error.
+    pub fn desc(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl fmt::Display for SndFileError {
+    fn fmt(&self, f : &mut fmt::Formatter) -> fmt::Result {
+        if let Some(operation) = self.operation {
+            write!(f, "{} failed", operation)?;
+            if let Some(ref target) = self.target {
+                write!(f, " on {}", target)?;
+            }
+            write!(f, ": ")?;
+        } else if let Some(ref target) = self.target {
+            write!(f, "{}: ", target)?;
+        }
+        write!(f, "{}", self.message)?;
+        if self.code() != ffi::SF_ERR_NO_ERROR {
+            write!(f, " (libsndfile error {})", self.code())?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for SndFileError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.source.as_ref().map(|err| err as &(dyn Error + 'static))
+    }
+}
+
+impl From<io::Error> for SndFileError {
+    fn from(err : io::Error) -> SndFileError {
+        let kind = ErrorKind::Io(err.kind());
+        SndFileError {
+            message : err.to_string(),
+            source : Some(err),
+            ..SndFileError::new(kind)
+        }
+    }
+}
+
+impl From<SndFileError> for io::Error {
+    fn from(err : SndFileError) -> io::Error {
+        io::Error::new(err.kind.io_kind(), err)
+    }
+}
+
+/// Type alias for a Result with SndFileError
+pub type SndFileResult<T> = Result<T, SndFileError>;
@@ -19,7 +19,7 @@
 // IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
 // CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
 
-#![allow(dead_code, non_camel_case_types)]
+#![allow(dead_code, non_camel_case_types, clippy::upper_case_acronyms)]
 
 use super::*;
 use libc::{c_char, c_void};
@@ -83,6 +83,7 @@ pub const SF_FORMAT_WVE : FORMAT_TYPE          = 0x190000;   // Psion WVE format
 pub const SF_FORMAT_OGG : FORMAT_TYPE          = 0x200000;   // Xiph OGG container
 pub const SF_FORMAT_MPC2K : FORMAT_TYPE        = 0x210000;   // Akai MPC 2000 sampler
 pub const SF_FORMAT_RF64 : FORMAT_TYPE         = 0x220000;   // RF64 WAV file
+pub const SF_FORMAT_MPEG : FORMAT_TYPE         = 0x230000;   // MPEG-1/2 audio stream
 /* Subtypes from here on. */
 pub const SF_FORMAT_PCM_S8 : FORMAT_TYPE       = 0x0001;     // Signed 8 bit data
 pub const SF_FORMAT_PCM_16 : FORMAT_TYPE       = 0x0002;     // Signed 16 bit data
@@ -97,6 +98,9 @@ pub const SF_FORMAT_IMA_ADPCM : FORMAT_TYPE    = 0x0012;     // IMA ADPCM
 pub const SF_FORMAT_MS_ADPCM : FORMAT_TYPE     = 0x0013;     // Microsoft ADPCM
 pub const SF_FORMAT_GSM610 : FORMAT_TYPE       = 0x0020;     // GSM 6.10 encoding
 pub const SF_FORMAT_VOX_ADPCM : FORMAT_TYPE    = 0x0021;     // Oki Dialogic ADPCM encoding
+pub const SF_FORMAT_NMS_ADPCM_16 : FORMAT_TYPE = 0x0022;     // 16kbs NMS G721-variant encoding
+pub const SF_FORMAT_NMS_ADPCM_24 : FORMAT_TYPE = 0x0023;     // 24kbs NMS G721-variant encoding
+pub const SF_FORMAT_NMS_ADPCM_32 : FORMAT_TYPE = 0x0024;     // 32kbs NMS G721-variant encoding
 pub const SF_FORMAT_G721_32 : FORMAT_TYPE      = 0x0030;     // 32kbs G721 ADPCM encoding
 pub const SF_FORMAT_G723_24 : FORMAT_TYPE      = 0x0031;     // 24kbs G723 ADPCM encoding
 pub const SF_FORMAT_G723_40 : FORMAT_TYPE      = 0x0032;     // 40kbs G723 ADPCM encoding
@@ -107,6 +111,14 @@ pub const SF_FORMAT_DWVW_N : FORMAT_TYPE       = 0x0043;     // N bit Delta Widt
 pub const SF_FORMAT_DPCM_8 : FORMAT_TYPE       = 0x0050;     // 8 bit differential PCM (XI only)
 pub const SF_FORMAT_DPCM_16 : FORMAT_TYPE      = 0x0051;     // 16 bit differential PCM (XI only)
 pub const SF_FORMAT_VORBIS : FORMAT_TYPE       = 0x0060;     // Xiph Vorbis encoding
+pub const SF_FORMAT_OPUS : FORMAT_TYPE         = 0x0064;     // Xiph/Skype Opus encoding
+pub const SF_FORMAT_ALAC_16 : FORMAT_TYPE      = 0x0070;     // Apple Lossless Audio Codec (16 bit)
+pub const SF_FORMAT_ALAC_20 : FORMAT_TYPE      = 0x0071;     // Apple Lossless Audio Codec (20 bit)
+pub const SF_FORMAT_ALAC_24 : FORMAT_TYPE      = 0x0072;     // Apple Lossless Audio Codec (24 bit)
+pub const SF_FORMAT_ALAC_32 : FORMAT_TYPE      = 0x0073;     // Apple Lossless Audio Codec (32 bit)
+pub const SF_FORMAT_MPEG_LAYER_I : FORMAT_TYPE = 0x0080;     // MPEG-1 Audio Layer I
+pub const SF_FORMAT_MPEG_LAYER_II : FORMAT_TYPE = 0x0081;    // MPEG-1 Audio Layer II
+pub const SF_FORMAT_MPEG_LAYER_III : FORMAT_TYPE = 0x0082;   // MPEG-2 Audio Layer III
 
 /* Endian-ness options. */
 
@@ -119,21 +131,164 @@ pub const SF_FORMAT_SUBMASK : FORMAT_TYPE      = 0x0000FFFF;
 pub const SF_FORMAT_TYPEMASK : FORMAT_TYPE     = 0x0FFF0000;
 pub const SF_FORMAT_ENDMASK : FORMAT_TYPE      = 0x30000000;
 
+pub type SF_COMMAND = i32;
+pub const SFC_GET_NORM_DOUBLE : SF_COMMAND          = 0x1010;
+pub const SFC_GET_NORM_FLOAT : SF_COMMAND           = 0x1011;
+pub const SFC_SET_NORM_DOUBLE : SF_COMMAND          = 0x1012;
+pub const SFC_SET_NORM_FLOAT : SF_COMMAND           = 0x1013;
+pub const SFC_SET_SCALE_FLOAT_INT_READ : SF_COMMAND = 0x1014;
+pub const SFC_SET_SCALE_INT_FLOAT_WRITE : SF_COMMAND = 0x1015;
+pub const SFC_GET_FORMAT_MAJOR_COUNT : SF_COMMAND   = 0x1030;
+pub const SFC_GET_FORMAT_MAJOR : SF_COMMAND         = 0x1031;
+pub const SFC_GET_FORMAT_SUBTYPE_COUNT : SF_COMMAND = 0x1032;
+pub const SFC_GET_FORMAT_SUBTYPE : SF_COMMAND       = 0x1033;
+pub const SFC_CALC_SIGNAL_MAX : SF_COMMAND          = 0x1040;
+pub const SFC_CALC_NORM_SIGNAL_MAX : SF_COMMAND     = 0x1041;
+pub const SFC_CALC_MAX_ALL_CHANNELS : SF_COMMAND    = 0x1042;
+pub const SFC_CALC_NORM_MAX_ALL_CHANNELS : SF_COMMAND = 0x1043;
+pub const SFC_GET_SIGNAL_MAX : SF_COMMAND           = 0x1044;
+pub const SFC_GET_MAX_ALL_CHANNELS : SF_COMMAND     = 0x1045;
+pub const SFC_SET_ADD_PEAK_CHUNK : SF_COMMAND       = 0x1050;
+pub const SFC_SET_CLIPPING : SF_COMMAND             = 0x10C0;
+pub const SFC_GET_CLIPPING : SF_COMMAND             = 0x10C1;
+pub const SFC_GET_EMBED_FILE_INFO : SF_COMMAND      = 0x10B0;
+pub const SFC_GET_CUE_COUNT : SF_COMMAND            = 0x10CD;
+pub const SFC_GET_CUE : SF_COMMAND                  = 0x10CE;
+pub const SFC_SET_CUE : SF_COMMAND                  = 0x10CF;
+pub const SFC_GET_INSTRUMENT : SF_COMMAND           = 0x10D0;
+pub const SFC_SET_INSTRUMENT : SF_COMMAND           = 0x10D1;
+pub const SFC_GET_LOOP_INFO : SF_COMMAND            = 0x10E0;
+pub const SFC_GET_BROADCAST_INFO : SF_COMMAND       = 0x10F0;
+pub const SFC_SET_BROADCAST_INFO : SF_COMMAND       = 0x10F1;
+pub const SFC_RAW_DATA_NEEDS_ENDSWAP : SF_COMMAND   = 0x1110;
+pub const SFC_SET_VBR_ENCODING_QUALITY : SF_COMMAND = 0x1300;
+pub const SFC_SET_COMPRESSION_LEVEL : SF_COMMAND    = 0x1301;
+pub const SFC_GET_BITRATE_MODE : SF_COMMAND         = 0x1304;
+pub const SFC_SET_BITRATE_MODE : SF_COMMAND         = 0x1305;
+
+pub const SF_BITRATE_MODE_CONSTANT : i32            = 0;
+pub const SF_BITRATE_MODE_AVERAGE : i32             = 1;
+pub const SF_BITRATE_MODE_VARIABLE : i32            = 2;
+
 pub type SNDFILE = c_void;
 
+#[repr(C)]
 pub struct FormatInfo {
     pub format : i32,
     pub name : *const c_char,
     pub extension : *const c_char
 }
 
+pub const SF_LOOP_NONE : i32                        = 800;
+pub const SF_LOOP_FORWARD : i32                     = 801;
+pub const SF_LOOP_BACKWARD : i32                    = 802;
+pub const SF_LOOP_ALTERNATING : i32                 = 803;
+
+/// Loop of an SF_INSTRUMENT
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct InstrumentLoop {
+    pub mode : i32,
+    pub start : u32,
+    pub end : u32,
+    pub count : u32
+}
+
+/// SF_INSTRUMENT
+#[repr(C)]
+pub struct Instrument {
+    pub gain : i32,
+    pub basenote : c_char,
+    pub detune : c_char,
+    pub velocity_lo : c_char,
+    pub velocity_hi : c_char,
+    pub key_lo : c_char,
+    pub key_hi : c_char,
+    pub loop_count : i32,
+    pub loops : [InstrumentLoop; 16]
+}
+
+/// SF_LOOP_INFO
+#[repr(C)]
+pub struct LoopInfo {
+    pub time_sig_num : i16,
+    pub time_sig_den : i16,
+    pub loop_mode : i32,
+    pub num_beats : i32,
+    pub bpm : f32,
+    pub root_key : i32,
+    pub future : [i32; 6]
+}
+
+/// SF_EMBED_FILE_INFO
+#[repr(C)]
+pub struct EmbedFileInfo {
+    pub offset : i64,
+    pub length : i64
+}
+
+/// SF_CUE_POINT
+#[repr(C)]
+pub struct CuePoint {
+    pub indx : i32,
+    pub position : u32,
+    pub fcc_chunk : i32,
+    pub chunk_start : i32,
+    pub block_start : i32,
+    pub sample_offset : u32,
+    pub name : [c_char; 256]
+}
+
+/// Size of the largest coding history kept by libsndfile.
+pub const BROADCAST_CODING_HISTORY_SIZE : usize = 16 * 1024;
+
+/// SF_BROADCAST_INFO_VAR(16 * 1024)
+#[repr(C)]
+pub struct BroadcastInfo {
+    pub description : [c_char; 256],
+    pub originator : [c_char; 32],
+    pub originator_reference : [c_char; 32],
+    pub origination_date : [c_char; 10],
+    pub origination_time : [c_char; 8],
+    pub time_reference_low : u32,
+    pub time_reference_high : u32,
+    pub version : i16,
+    pub umid : [c_char; 64],
+    pub loudness_value : i16,
+    pub loudness_range : i16,
+    pub max_true_peak_level : i16,
+    pub max_momentary_loudness : i16,
+    pub max_shortterm_loudness : i16,
+    pub reserved : [c_char; 180],
+    pub coding_history_size : u32,
+    pub coding_history : [c_char; BROADCAST_CODING_HISTORY_SIZE]
+}
+
+pub type VioGetFilelen = extern "C" fn(user_data : *mut c_void) -> i64;
+pub type VioSeek = extern "C" fn(offset : i64, whence : i32, user_data : *mut c_void) -> i64;
+pub type VioRead = extern "C" fn(ptr : *mut c_void, count : i64, user_data : *mut c_void) -> i64;
+pub type VioWrite = extern "C" fn(ptr : *const c_void, count : i64, user_data : *mut c_void) -> i64;
+pub type VioTell = extern "C" fn(user_data : *mut c_void) -> i64;
+
+#[repr(C)]
+pub struct VirtualIo {
+    pub get_filelen : VioGetFilelen,
+    pub seek : VioSeek,
+    pub read : VioRead,
+    pub write : VioWrite,
+    pub tell : VioTell
+}
+
 extern "C" {
-    pub fn sf_open(path : *const c_char, mode : SF_MODE, info : *const SndInfo) -> *mut SNDFILE;
-    pub fn sf_open_fd(fd : i32, mode : SF_MODE, info : *const SndInfo, close_desc : SF_BOOL) -> *mut SNDFILE;
+    pub fn sf_open(path : *const c_char, mode : SF_MODE, info : *mut SndInfo) -> *mut SNDFILE;
+    pub fn sf_open_fd(fd : i32, mode : SF_MODE, info : *mut SndInfo, close_desc : SF_BOOL) -> *mut SNDFILE;
+    #[cfg(windows)]
+    pub fn sf_wchar_open(wpath : *const u16, mode : SF_MODE, info : *mut SndInfo) -> *mut SNDFILE;
+    pub fn sf_open_virtual(sfvirtual : *mut VirtualIo, mode : SF_MODE, info : *mut SndInfo, user_data : *mut c_void) -> *mut SNDFILE;
     pub fn sf_format_check(info : *const SndInfo) -> SF_BOOL;
 
     pub fn sf_seek(sndfile : *mut SNDFILE, frames : i64, whence : i32) -> i64;
-    pub fn sf_command(sndfile : *mut SNDFILE, cmd : i32, data : *mut c_void, datasize : i32) -> SF_ERR;
+    pub fn sf_command(sndfile : *mut SNDFILE, cmd : SF_COMMAND, data : *mut c_void, datasize : i32) -> SF_ERR;
 
     pub fn sf_error(sndfile : *mut SNDFILE) -> SF_ERR;
     pub fn sf_strerror(sndfile : *mut SNDFILE) -> *const c_char;
@@ -155,18 +310,18 @@ extern "C" {
     pub fn sf_readf_float(sndfile : *mut SNDFILE, ptr : *mut f32, frames : i64) -> i64;
     pub fn sf_readf_double(sndfile : *mut SNDFILE, ptr : *mut f64, frames : i64) -> i64;
 
-    pub fn sf_write_short(sndfile : *mut SNDFILE, ptr : *mut i16, items : i64) -> i64;
-    pub fn sf_write_int(sndfile : *mut SNDFILE, ptr : *mut i32, items : i64) -> i64;
-    pub fn sf_write_float(sndfile : *mut SNDFILE, ptr : *mut f32, items : i64) -> i64;
-    pub fn sf_write_double(sndfile : *mut SNDFILE, ptr : *mut f64, items : i64) -> i64;
+    pub fn sf_write_short(sndfile : *mut SNDFILE, ptr : *const i16, items : i64) -> i64;
+    pub fn sf_write_int(sndfile : *mut SNDFILE, ptr : *const i32, items : i64) -> i64;
+    pub fn sf_write_float(sndfile : *mut SNDFILE, ptr : *const f32, items : i64) -> i64;
+    pub fn sf_write_double(sndfile : *mut SNDFILE, ptr : *const f64, items : i64) -> i64;
 
-    pub fn sf_writef_short(sndfile : *mut SNDFILE, ptr : *mut i16, frames : i64) -> i64;
-    pub fn sf_writef_int(sndfile : *mut SNDFILE, ptr : *mut i32, frames : i64) -> i64;
-    pub fn sf_writef_float(sndfile : *mut SNDFILE, ptr : *mut f32, frames : i64) -> i64;
-    pub fn sf_writef_double(sndfile : *mut SNDFILE, ptr : *mut f64, frames : i64) -> i64;
+    pub fn sf_writef_short(sndfile : *mut SNDFILE, ptr : *const i16, frames : i64) -> i64;
+    pub fn sf_writef_int(sndfile : *mut SNDFILE, ptr : *const i32, frames : i64) -> i64;
+    pub fn sf_writef_float(sndfile : *mut SNDFILE, ptr : *const f32, frames : i64) -> i64;
+    pub fn sf_writef_double(sndfile : *mut SNDFILE, ptr : *const f64, frames : i64) -> i64;
 
     pub fn sf_read_raw(sndfile : *mut SNDFILE, ptr : *mut c_void, bytes : i64) -> i64;
-    pub fn sf_write_raw(sndfile : *mut SNDFILE, ptr : *mut c_void, bytes : i64) -> i64;
+    pub fn sf_write_raw(sndfile : *mut SNDFILE, ptr : *const c_void, bytes : i64) -> i64;
 
     pub fn sf_get_string(sndfile : *mut SNDFILE, str_type : i32) -> *const c_char;
     pub fn sf_set_string(sndfile : *mut SNDFILE, str_type : i32, string : *const c_char) -> SF_ERR;
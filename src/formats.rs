@@ -0,0 +1,518 @@
+// The MIT License (MIT)
+//
+// Copyright (c) 2013 Jeremy Letang (letang.jeremy@gmail.com)
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+//! Typed description of the format of a sound file.
+//!
+//! A libsndfile format is a major format (the container), a subtype (the
+//! encoding of the samples) and an endian-ness, OR-ed together in an `i32`.
+//!
+//! The functions of this module list the formats known by the libsndfile
+//! linked at runtime, which depend on how it was built.
+
+use std::ffi::CStr;
+use std::mem;
+use std::ptr;
+use libc::c_void;
+
+use ffi;
+use {SndFile, SndInfo, StringSoundType};
+
+macro_rules! format_enum {
+    ($(#[$attr:meta])* pub enum $name:ident {
+        $($(#[$vattr:meta])* $variant:ident = $raw:path),+
+    }) => {
+        $(#[$attr])*
+        #[derive(Clone, PartialEq, Eq, Hash, Debug, Copy)]
+        pub enum $name {
+            $($(#[$vattr])* $variant,)+
+            /// A value not known by this binding, kept as is
+            Unknown(i32)
+        }
+
+        impl $name {
+            /// Get the raw libsndfile value of the format.
+            pub fn to_raw(self) -> i32 {
+                match self {
+                    $($name::$variant => $raw,)+
+                    $name::Unknown(raw) => raw
+                }
+            }
+
+            /// Get the format from its raw libsndfile value.
+            pub fn from_raw(raw : i32) -> $name {
+                match raw {
+                    $($raw => $name::$variant,)+
+                    _ => $name::Unknown(raw)
+                }
+            }
+        }
+
+        #[cfg(test)]
+        impl $name {
+            /// All the formats known by this binding.
+            fn known() -> &'static [$name] {
+                &[$($name::$variant),+]
+            }
+        }
+    }
+}
+
+format_enum! {
+    /// The major format of a file, that is its container
+    pub enum MajorFormat {
+        /// Microsoft WAV format (little endian)
+        Wav = ffi::SF_FORMAT_WAV,
+        /// Apple/SGI AIFF format (big endian)
+        Aiff = ffi::SF_FORMAT_AIFF,
+        /// Sun/NeXT AU format (big endian)
+        Au = ffi::SF_FORMAT_AU,
+        /// RAW PCM data
+        Raw = ffi::SF_FORMAT_RAW,
+        /// Ensoniq PARIS file format
+        Paf = ffi::SF_FORMAT_PAF,
+        /// Amiga IFF / SVX8 / SV16 format
+        Svx = ffi::SF_FORMAT_SVX,
+        /// Sphere NIST format
+        Nist = ffi::SF_FORMAT_NIST,
+        /// VOC files
+        Voc = ffi::SF_FORMAT_VOC,
+        /// Berkeley/IRCAM/CARL
+        Ircam = ffi::SF_FORMAT_IRCAM,
+        /// Sonic Foundry's 64 bit RIFF/WAV
+        W64 = ffi::SF_FORMAT_W64,
+        /// Matlab (tm) V4.2 / GNU Octave 2.0
+        Mat4 = ffi::SF_FORMAT_MAT4,
+        /// Matlab (tm) V5.0 / GNU Octave 2.1
+        Mat5 = ffi::SF_FORMAT_MAT5,
+        /// Portable Voice Format
+        Pvf = ffi::SF_FORMAT_PVF,
+        /// Fasttracker 2 Extended Instrument
+        Xi = ffi::SF_FORMAT_XI,
+        /// HMM Tool Kit format
+        Htk = ffi::SF_FORMAT_HTK,
+        /// Midi Sample Dump Standard
+        Sds = ffi::SF_FORMAT_SDS,
+        /// Audio Visual Research
+        Avr = ffi::SF_FORMAT_AVR,
+        /// MS WAVE with WAVEFORMATEX
+        Wavex = ffi::SF_FORMAT_WAVEX,
+        /// Sound Designer 2
+        Sd2 = ffi::SF_FORMAT_SD2,
+        /// FLAC lossless file format
+        Flac = ffi::SF_FORMAT_FLAC,
+        /// Core Audio File format
+        Caf = ffi::SF_FORMAT_CAF,
+        /// Psion WVE format
+        Wve = ffi::SF_FORMAT_WVE,
+        /// Xiph OGG container
+        Ogg = ffi::SF_FORMAT_OGG,
+        /// Akai MPC 2000 sampler
+        Mpc2k = ffi::SF_FORMAT_MPC2K,
+        /// RF64 WAV file
+        Rf64 = ffi::SF_FORMAT_RF64,
+        /// MPEG-1/2 audio stream
+        Mpeg = ffi::SF_FORMAT_MPEG
+    }
+}
+
+impl MajorFormat {
+    /// Whether the linked libsndfile supports the major format, which
+    /// depends on the libraries it was built with.
+    pub fn is_supported(self) -> bool {
+        major_formats().any(|info| info.format == self)
+    }
+
+    /**
+     * List the types of tag libsndfile can write in files of this major
+     * format.
+     *
+     * Formats for which libsndfile has no string support, or whose support
+     * is not known by this binding, return an empty list.
+     */
+    pub fn supported_strings(self) -> &'static [StringSoundType] {
+        use StringSoundType::*;
+
+        match self {
+            MajorFormat::Wav | MajorFormat::Wavex | MajorFormat::Rf64 =>
+                &[Title, Copyright, Software, Artist, Comment, Date, Album, TrackNumber, Genre],
+            MajorFormat::Aiff => &[Title, Copyright, Software, Artist, Comment],
+            MajorFormat::Mpeg => &[Title, Artist, Comment, Date, Album, TrackNumber, Genre],
+            MajorFormat::Flac | MajorFormat::Ogg =>
+                &[Title, Copyright, Software, Artist, Comment, Date, Album, License,
+                  TrackNumber, Genre],
+            _ => &[]
+        }
+    }
+}
+
+format_enum! {
+    /// The subtype of a file, that is the encoding of its samples
+    pub enum SubtypeFormat {
+        /// Signed 8 bit data
+        PcmS8 = ffi::SF_FORMAT_PCM_S8,
+        /// Signed 16 bit data
+        Pcm16 = ffi::SF_FORMAT_PCM_16,
+        /// Signed 24 bit data
+        Pcm24 = ffi::SF_FORMAT_PCM_24,
+        /// Signed 32 bit data
+        Pcm32 = ffi::SF_FORMAT_PCM_32,
+        /// Unsigned 8 bit data (WAV and RAW only)
+        PcmU8 = ffi::SF_FORMAT_PCM_U8,
+        /// 32 bit float data
+        Float = ffi::SF_FORMAT_FLOAT,
+        /// 64 bit float data
+        Double = ffi::SF_FORMAT_DOUBLE,
+        /// U-Law encoded
+        Ulaw = ffi::SF_FORMAT_ULAW,
+        /// A-Law encoded
+        Alaw = ffi::SF_FORMAT_ALAW,
+        /// IMA ADPCM
+        ImaAdpcm = ffi::SF_FORMAT_IMA_ADPCM,
+        /// Microsoft ADPCM
+        MsAdpcm = ffi::SF_FORMAT_MS_ADPCM,
+        /// GSM 6.10 encoding
+        Gsm610 = ffi::SF_FORMAT_GSM610,
+        /// Oki Dialogic ADPCM encoding
+        VoxAdpcm = ffi::SF_FORMAT_VOX_ADPCM,
+        /// 16kbs NMS G721-variant encoding
+        NmsAdpcm16 = ffi::SF_FORMAT_NMS_ADPCM_16,
+        /// 24kbs NMS G721-variant encoding
+        NmsAdpcm24 = ffi::SF_FORMAT_NMS_ADPCM_24,
+        /// 32kbs NMS G721-variant encoding
+        NmsAdpcm32 = ffi::SF_FORMAT_NMS_ADPCM_32,
+        /// 32kbs G721 ADPCM encoding
+        G721_32 = ffi::SF_FORMAT_G721_32,
+        /// 24kbs G723 ADPCM encoding
+        G723_24 = ffi::SF_FORMAT_G723_24,
+        /// 40kbs G723 ADPCM encoding
+        G723_40 = ffi::SF_FORMAT_G723_40,
+        /// 12 bit Delta Width Variable Word encoding
+        Dwvw12 = ffi::SF_FORMAT_DWVW_12,
+        /// 16 bit Delta Width Variable Word encoding
+        Dwvw16 = ffi::SF_FORMAT_DWVW_16,
+        /// 24 bit Delta Width Variable Word encoding
+        Dwvw24 = ffi::SF_FORMAT_DWVW_24,
+        /// N bit Delta Width Variable Word encoding
+        DwvwN = ffi::SF_FORMAT_DWVW_N,
+        /// 8 bit differential PCM (XI only)
+        Dpcm8 = ffi::SF_FORMAT_DPCM_8,
+        /// 16 bit differential PCM (XI only)
+        Dpcm16 = ffi::SF_FORMAT_DPCM_16,
+        /// Xiph Vorbis encoding
+        Vorbis = ffi::SF_FORMAT_VORBIS,
+        /// Xiph/Skype Opus encoding
+        Opus = ffi::SF_FORMAT_OPUS,
+        /// Apple Lossless Audio Codec (16 bit)
+        Alac16 = ffi::SF_FORMAT_ALAC_16,
+        /// Apple Lossless Audio Codec (20 bit)
+        Alac20 = ffi::SF_FORMAT_ALAC_20,
+        /// Apple Lossless Audio Codec (24 bit)
+        Alac24 = ffi::SF_FORMAT_ALAC_24,
+        /// Apple Lossless Audio Codec (32 bit)
+        Alac32 = ffi::SF_FORMAT_ALAC_32,
+        /// MPEG-1 Audio Layer I
+        MpegLayerI = ffi::SF_FORMAT_MPEG_LAYER_I,
+        /// MPEG-1 Audio Layer II
+        MpegLayerII = ffi::SF_FORMAT_MPEG_LAYER_II,
+        /// MPEG-2 Audio Layer III
+        MpegLayerIII = ffi::SF_FORMAT_MPEG_LAYER_III
+    }
+}
+
+impl SubtypeFormat {
+    /// Whether the linked libsndfile supports the subtype, which depends on
+    /// the libraries it was built with.
+    pub fn is_supported(self) -> bool {
+        subtype_formats().any(|info| info.format == self)
+    }
+}
+
+/// The endian-ness of a file
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Copy)]
+pub enum Endian {
+    /// Default file endian-ness
+    File,
+    /// Force little endian-ness
+    Little,
+    /// Force big endian-ness
+    Big,
+    /// Force CPU endian-ness
+    Cpu
+}
+
+impl Endian {
+    /// Get the raw libsndfile value of the endian-ness.
+    pub fn to_raw(self) -> i32 {
+        match self {
+            Endian::File => ffi::SF_ENDIAN_FILE,
+            Endian::Little => ffi::SF_ENDIAN_LITTLE,
+            Endian::Big => ffi::SF_ENDIAN_BIG,
+            Endian::Cpu => ffi::SF_ENDIAN_CPU
+        }
+    }
+
+    /// Get the endian-ness from its raw libsndfile value, ignoring the bits
+    /// outside of SF_FORMAT_ENDMASK.
+    pub fn from_raw(raw : i32) -> Endian {
+        match raw & ffi::SF_FORMAT_ENDMASK {
+            ffi::SF_ENDIAN_LITTLE => Endian::Little,
+            ffi::SF_ENDIAN_BIG => Endian::Big,
+            ffi::SF_ENDIAN_CPU => Endian::Cpu,
+            _ => Endian::File
+        }
+    }
+}
+
+/// The complete format of a file, convertible losslessly from and to the
+/// raw `i32` stored in SndInfo.
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Copy)]
+pub struct Format {
+    major : MajorFormat,
+    subtype : SubtypeFormat,
+    endian : Endian,
+    // Bits outside of the type, sub and endian masks.
+    extra : i32
+}
+
+impl Format {
+    /// Create a format with the default file endian-ness.
+    pub fn new(major : MajorFormat, subtype : SubtypeFormat) -> Format {
+        Format {
+            major,
+            subtype,
+            endian : Endian::File,
+            extra : 0
+        }
+    }
+
+    /// Return the same format with the given endian-ness.
+    pub fn with_endian(self, endian : Endian) -> Format {
+        Format {
+            endian,
+            ..self
+        }
+    }
+
+    /// The major format.
+    pub fn major(&self) -> MajorFormat {
+        self.major
+    }
+
+    /// The subtype.
+    pub fn subtype(&self) -> SubtypeFormat {
+        self.subtype
+    }
+
+    /// The endian-ness.
+    pub fn endian(&self) -> Endian {
+        self.endian
+    }
+
+    /// Split a raw libsndfile format into its parts.
+    pub fn from_raw(raw : i32) -> Format {
+        let known = ffi::SF_FORMAT_TYPEMASK | ffi::SF_FORMAT_SUBMASK | ffi::SF_FORMAT_ENDMASK;
+        Format {
+            major : MajorFormat::from_raw(raw & ffi::SF_FORMAT_TYPEMASK),
+            subtype : SubtypeFormat::from_raw(raw & ffi::SF_FORMAT_SUBMASK),
+            endian : Endian::from_raw(raw),
+            extra : raw & !known
+        }
+    }
+
+    /// Build the raw libsndfile format.
+    pub fn to_raw(&self) -> i32 {
+        (self.major.to_raw() & ffi::SF_FORMAT_TYPEMASK)
+            | (self.subtype.to_raw() & ffi::SF_FORMAT_SUBMASK)
+            | self.endian.to_raw()
+            | self.extra
+    }
+}
+
+impl Format {
+    /// Whether the encoder of the format has a variable bit rate quality.
+    pub fn supports_vbr_quality(&self) -> bool {
+        matches!(self.subtype,
+                 SubtypeFormat::Vorbis | SubtypeFormat::Opus | SubtypeFormat::MpegLayerIII)
+    }
+
+    /// Whether the encoder of the format has a compression level.
+    pub fn supports_compression_level(&self) -> bool {
+        self.major == MajorFormat::Flac || self.supports_vbr_quality()
+    }
+
+    /// Whether libsndfile can add a PEAK chunk to files of the format, which
+    /// holds for WAV, AIFF, CAF and RF64 float files.
+    pub fn supports_peak_chunk(&self) -> bool {
+        matches!(self.major,
+                 MajorFormat::Wav | MajorFormat::Wavex | MajorFormat::Aiff
+                 | MajorFormat::Caf | MajorFormat::Rf64)
+            && matches!(self.subtype, SubtypeFormat::Float | SubtypeFormat::Double)
+    }
+
+    /// Whether the encoder of the format has a bit rate mode.
+    pub fn supports_bitrate_mode(&self) -> bool {
+        matches!(self.subtype, SubtypeFormat::Opus | SubtypeFormat::MpegLayerIII)
+    }
+}
+
+impl From<i32> for Format {
+    fn from(raw : i32) -> Format {
+        Format::from_raw(raw)
+    }
+}
+
+impl From<Format> for i32 {
+    fn from(format : Format) -> i32 {
+        format.to_raw()
+    }
+}
+
+/// A format known by the linked libsndfile, as returned by major_formats()
+/// and subtype_formats()
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct FormatInfo<F> {
+    /// The format
+    pub format : F,
+    /// The name of the format, as given by libsndfile
+    pub name : String,
+    /// The usual file extension of the format, None for subtypes
+    pub extension : Option<String>
+}
+
+fn format_count(cmd : ffi::SF_COMMAND) -> i32 {
+    let mut count : i32 = 0;
+    unsafe {
+        ffi::sf_command(ptr::null_mut(),
+                        cmd,
+                        &mut count as *mut i32 as *mut c_void,
+                        mem::size_of::<i32>() as i32);
+    }
+    count
+}
+
+fn format_info(cmd : ffi::SF_COMMAND, index : i32) -> Option<FormatInfo<i32>> {
+    let mut info = ffi::FormatInfo {
+        format : index,
+        name : ptr::null(),
+        extension : ptr::null()
+    };
+    let res = unsafe {
+        ffi::sf_command(ptr::null_mut(),
+                        cmd,
+                        &mut info as *mut ffi::FormatInfo as *mut c_void,
+                        mem::size_of::<ffi::FormatInfo>() as i32)
+    };
+    if res != 0 || info.name.is_null() {
+        return None;
+    }
+    let to_string = |c_str| unsafe {
+        CStr::from_ptr(c_str).to_string_lossy().into_owned()
+    };
+    Some(FormatInfo {
+        format : info.format,
+        name : to_string(info.name),
+        extension : if info.extension.is_null() {
+            None
+        } else {
+            Some(to_string(info.extension))
+        }
+    })
+}
+
+/// List the major formats supported by the linked libsndfile.
+pub fn major_formats() -> impl Iterator<Item = FormatInfo<MajorFormat>> {
+    (0..format_count(ffi::SFC_GET_FORMAT_MAJOR_COUNT))
+        .filter_map(|i| format_info(ffi::SFC_GET_FORMAT_MAJOR, i))
+        .map(|info| FormatInfo {
+            format : MajorFormat::from_raw(info.format & ffi::SF_FORMAT_TYPEMASK),
+            name : info.name,
+            extension : info.extension
+        })
+}
+
+/// List the subtypes supported by the linked libsndfile, whatever the major
+/// format.
+pub fn subtype_formats() -> impl Iterator<Item = FormatInfo<SubtypeFormat>> {
+    (0..format_count(ffi::SFC_GET_FORMAT_SUBTYPE_COUNT))
+        .filter_map(|i| format_info(ffi::SFC_GET_FORMAT_SUBTYPE, i))
+        .map(|info| FormatInfo {
+            format : SubtypeFormat::from_raw(info.format & ffi::SF_FORMAT_SUBMASK),
+            name : info.name,
+            extension : info.extension
+        })
+}
+
+/**
+ * List the subtypes the linked libsndfile can write in a major format.
+ *
+ * Each subtype is probed with sf_format_check for a mono file at 48000 Hz.
+ *
+ * # Argument
+ * * `major` - The major format to probe
+ *
+ * Return the supported subtypes, in libsndfile's order.
+ */
+pub fn supported_subtypes(major : MajorFormat) -> Vec<SubtypeFormat> {
+    subtype_formats()
+        .map(|info| info.format)
+        .filter(|&subtype| {
+            let info = SndInfo {
+                samplerate : 48000,
+                channels : 1,
+                format : Format::new(major, subtype).to_raw(),
+                ..SndInfo::default()
+            };
+            SndFile::check_format(&info)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_raw_round_trip() {
+        let mut majors = MajorFormat::known().to_vec();
+        majors.push(MajorFormat::Unknown(0x0FF0_0000));
+        let mut subtypes = SubtypeFormat::known().to_vec();
+        subtypes.push(SubtypeFormat::Unknown(0x7777));
+        let endians = [Endian::File, Endian::Little, Endian::Big, Endian::Cpu];
+        // Bits outside of the type, sub and endian masks.
+        let extras = [0, 0x4000_0000, i32::MIN, i32::MIN | 0x4000_0000];
+
+        for &major in &majors {
+            for &subtype in &subtypes {
+                for &endian in &endians {
+                    for &extra in &extras {
+                        let raw = Format::new(major, subtype).with_endian(endian).to_raw() | extra;
+                        let format = Format::from_raw(raw);
+                        assert_eq!(format.major(), major);
+                        assert_eq!(format.subtype(), subtype);
+                        assert_eq!(format.endian(), endian);
+                        assert_eq!(format.to_raw(), raw);
+                        assert_eq!(i32::from(Format::from(raw)), raw);
+                    }
+                }
+            }
+        }
+    }
+}
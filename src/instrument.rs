@@ -0,0 +1,274 @@
+// The MIT License (MIT)
+//
+// Copyright (c) 2013 Jeremy Letang (letang.jeremy@gmail.com)
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+//! Sampler metadata: the instrument of `smpl`/`inst` chunks and the loop
+//! information of acidized files.
+
+use std::mem;
+use libc::c_char;
+
+use ffi;
+use mode::{Mode, Writable};
+use {ErrorKind, SndFile, SndFileError, SndFileResult};
+
+/// Maximum number of loops of an instrument
+const MAX_LOOPS : usize = 16;
+
+/// How a loop is played
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default)]
+pub enum LoopMode {
+    /// No loop
+    #[default]
+    None,
+    /// Loop forward
+    Forward,
+    /// Loop backward
+    Backward,
+    /// Loop forward then backward
+    Alternating,
+    /// Loop mode unknown to this binding, with its raw libsndfile value
+    Unknown(i32)
+}
+
+impl LoopMode {
+    /// Get the loop mode from its libsndfile value.
+    pub fn from_raw(raw : i32) -> LoopMode {
+        match raw {
+            ffi::SF_LOOP_NONE => LoopMode::None,
+            ffi::SF_LOOP_FORWARD => LoopMode::Forward,
+            ffi::SF_LOOP_BACKWARD => LoopMode::Backward,
+            ffi::SF_LOOP_ALTERNATING => LoopMode::Alternating,
+            _ => LoopMode::Unknown(raw)
+        }
+    }
+
+    /// Get the libsndfile value of the loop mode.
+    pub fn to_raw(self) -> i32 {
+        match self {
+            LoopMode::None => ffi::SF_LOOP_NONE,
+            LoopMode::Forward => ffi::SF_LOOP_FORWARD,
+            LoopMode::Backward => ffi::SF_LOOP_BACKWARD,
+            LoopMode::Alternating => ffi::SF_LOOP_ALTERNATING,
+            LoopMode::Unknown(raw) => raw
+        }
+    }
+}
+
+/// A sustain or release loop of an instrument
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct InstrumentLoop {
+    /// How the loop is played
+    pub mode : LoopMode,
+    /// First frame of the loop
+    pub start : u32,
+    /// Frame after the end of the loop
+    pub end : u32,
+    /// Number of times the loop is played, 0 for infinite
+    pub count : u32
+}
+
+/// The sampler instrument of a file, from its `smpl` or `inst` chunk
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct Instrument {
+    /// Gain, in dB
+    pub gain : i32,
+    /// MIDI note of the recorded sound
+    pub base_note : u8,
+    /// Detuning of the recorded sound, in cents
+    pub detune : i8,
+    /// Lowest velocity the instrument plays at
+    pub velocity_lo : u8,
+    /// Highest velocity the instrument plays at
+    pub velocity_hi : u8,
+    /// Lowest MIDI note the instrument plays at
+    pub key_lo : u8,
+    /// Highest MIDI note the instrument plays at
+    pub key_hi : u8,
+    /// Sustain and release loops, up to 16
+    pub loops : Vec<InstrumentLoop>
+}
+
+impl Instrument {
+    // c_char is unsigned on some targets
+    #[allow(clippy::unnecessary_cast)]
+    fn from_ffi(instrument : &ffi::Instrument) -> Instrument {
+        let count = (instrument.loop_count.max(0) as usize).min(MAX_LOOPS);
+        Instrument {
+            gain : instrument.gain,
+            base_note : instrument.basenote as u8,
+            detune : instrument.detune as i8,
+            velocity_lo : instrument.velocity_lo as u8,
+            velocity_hi : instrument.velocity_hi as u8,
+            key_lo : instrument.key_lo as u8,
+            key_hi : instrument.key_hi as u8,
+            loops : instrument.loops[..count].iter().map(|l| InstrumentLoop {
+                mode : LoopMode::from_raw(l.mode),
+                start : l.start,
+                end : l.end,
+                count : l.count
+            }).collect()
+        }
+    }
+
+    fn to_ffi(&self, instrument : &mut ffi::Instrument) {
+        instrument.gain = self.gain;
+        instrument.basenote = self.base_note as c_char;
+        instrument.detune = self.detune as c_char;
+        instrument.velocity_lo = self.velocity_lo as c_char;
+        instrument.velocity_hi = self.velocity_hi as c_char;
+        instrument.key_lo = self.key_lo as c_char;
+        instrument.key_hi = self.key_hi as c_char;
+        instrument.loop_count = self.loops.len() as i32;
+        for (raw, l) in instrument.loops.iter_mut().zip(self.loops.iter()) {
+            raw.mode = l.mode.to_raw();
+            raw.start = l.start;
+            raw.end = l.end;
+            raw.count = l.count;
+        }
+    }
+}
+
+/// The loop information of an acidized file
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct LoopInfo {
+    /// Numerator of the time signature
+    pub time_sig_num : i16,
+    /// Denominator of the time signature
+    pub time_sig_den : i16,
+    /// How the file is played
+    pub loop_mode : LoopMode,
+    /// Number of beats in the file
+    pub num_beats : i32,
+    /// Tempo, in beats per minute
+    pub bpm : f32,
+    /// MIDI note of the file
+    pub root_key : i32
+}
+
+impl<M : Mode> SndFile<M> {
+    /**
+     * Read the sampler instrument of the file.
+     *
+     * Return Some(Instrument) if the file has an instrument, None otherwise.
+     */
+    pub fn instrument(&self) -> Option<Instrument> {
+        let mut instrument : ffi::Instrument = unsafe { mem::zeroed() };
+        match self.command(ffi::SFC_GET_INSTRUMENT, &mut instrument) {
+            ffi::SF_TRUE => Some(Instrument::from_ffi(&instrument)),
+            _ => None
+        }
+    }
+
+    /**
+     * Read the loop information of an acidized file.
+     *
+     * libsndfile cannot write loop information, so there is no setter.
+     *
+     * Return Some(LoopInfo) if the file has loop information, None otherwise.
+     */
+    pub fn loop_info(&self) -> Option<LoopInfo> {
+        let mut info : ffi::LoopInfo = unsafe { mem::zeroed() };
+        match self.command(ffi::SFC_GET_LOOP_INFO, &mut info) {
+            ffi::SF_TRUE => Some(LoopInfo {
+                time_sig_num : info.time_sig_num,
+                time_sig_den : info.time_sig_den,
+                loop_mode : LoopMode::from_raw(info.loop_mode),
+                num_beats : info.num_beats,
+                bpm : info.bpm,
+                root_key : info.root_key
+            }),
+            _ => None
+        }
+    }
+}
+
+impl<M : Writable> SndFile<M> {
+    /**
+     * Set the sampler instrument written to the file.
+     *
+     * This must be done before writing any audio data.
+     *
+     * # Argument
+     * * `instrument` - The instrument of the file
+     *
+     * Return () on success, InvalidValue if there are more than 16 loops,
+     * the error of libsndfile otherwise, or UnsupportedCommand if the format
+     * has no instrument.
+     */
+    pub fn set_instrument(&mut self, instrument : &Instrument) -> SndFileResult<()> {
+        if instrument.loops.len() > MAX_LOOPS {
+            return Err(SndFileError::new(ErrorKind::InvalidValue)
+                .during("SFC_SET_INSTRUMENT")
+                .on(&self.origin));
+        }
+        let mut raw : ffi::Instrument = unsafe { mem::zeroed() };
+        instrument.to_ffi(&mut raw);
+        if self.command(ffi::SFC_SET_INSTRUMENT, &mut raw) == ffi::SF_TRUE {
+            Ok(())
+        } else {
+            Err(self.command_error(ErrorKind::UnsupportedCommand, "SFC_SET_INSTRUMENT"))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn loop_mode_raw_round_trip() {
+        let modes = [LoopMode::None, LoopMode::Forward, LoopMode::Backward,
+                     LoopMode::Alternating, LoopMode::Unknown(0x7777)];
+        for &mode in &modes {
+            assert_eq!(LoopMode::from_raw(mode.to_raw()), mode);
+        }
+    }
+
+    #[test]
+    fn ffi_round_trip() {
+        let instrument = Instrument {
+            gain : -6,
+            base_note : 200,
+            detune : -50,
+            velocity_lo : 1,
+            velocity_hi : 255,
+            key_lo : 0,
+            key_hi : 127,
+            loops : vec![
+                InstrumentLoop { mode : LoopMode::Forward, start : 10, end : 20, count : 0 },
+                InstrumentLoop { mode : LoopMode::Unknown(0x7777), start : 30, end : 40, count : 3 }
+            ]
+        };
+        let mut raw : ffi::Instrument = unsafe { mem::zeroed() };
+        instrument.to_ffi(&mut raw);
+        assert_eq!(raw.loop_count, 2);
+        assert_eq!(Instrument::from_ffi(&raw), instrument);
+    }
+
+    #[test]
+    fn from_ffi_bounds_loop_count() {
+        let mut raw : ffi::Instrument = unsafe { mem::zeroed() };
+        raw.loop_count = -1;
+        assert!(Instrument::from_ffi(&raw).loops.is_empty());
+        raw.loop_count = 100;
+        assert_eq!(Instrument::from_ffi(&raw).loops.len(), MAX_LOOPS);
+    }
+}
@@ -0,0 +1,164 @@
+// The MIT License (MIT)
+//
+// Copyright (c) 2013 Jeremy Letang (letang.jeremy@gmail.com)
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+//! Iterators reading a file frame by frame or chunk by chunk until its end.
+
+use mode::Readable;
+use {Sample, SndFile, SndFileResult};
+
+/// Number of frames buffered by Frames.
+const FRAMES_BUFFER : usize = 1024;
+
+/**
+ * Iterator over chunks of interleaved frames, returned by SndFile::chunks.
+ *
+ * Every chunk holds the requested number of frames, except the last one
+ * which holds the remaining frames. The iterator yields owned buffers,
+ * next_chunk borrows its internal buffer instead.
+ */
+pub struct Chunks<'a, M : Readable, T : Sample> {
+    file : &'a mut SndFile<M>,
+    buffer : Vec<T>,
+    done : bool
+}
+
+impl<'a, M : Readable, T : Sample> Chunks<'a, M, T> {
+    fn new(file : &'a mut SndFile<M>, frames_per_chunk : usize) -> Chunks<'a, M, T> {
+        let len = frames_per_chunk.max(1) * file.channels();
+        Chunks {
+            file,
+            buffer : vec![T::default(); len],
+            done : false
+        }
+    }
+
+    /**
+     * Read the next chunk into the internal buffer.
+     *
+     * Return Ok() containing the frames read, None at the end of the file,
+     * the error otherwise. No chunk is read after an error.
+     */
+    pub fn next_chunk(&mut self) -> SndFileResult<Option<&[T]>> {
+        if self.done {
+            return Ok(None);
+        }
+        let frames = match self.file.read_frames(&mut self.buffer) {
+            Ok(frames) => frames,
+            Err(err) => {
+                self.done = true;
+                return Err(err);
+            }
+        };
+        if frames == 0 {
+            self.done = true;
+            return Ok(None);
+        }
+        Ok(Some(&self.buffer[..frames * self.file.channels()]))
+    }
+}
+
+impl<'a, M : Readable, T : Sample> Iterator for Chunks<'a, M, T> {
+    type Item = SndFileResult<Vec<T>>;
+
+    fn next(&mut self) -> Option<SndFileResult<Vec<T>>> {
+        match self.next_chunk() {
+            Ok(Some(chunk)) => Some(Ok(chunk.to_vec())),
+            Ok(None) => None,
+            Err(err) => Some(Err(err))
+        }
+    }
+}
+
+/**
+ * Iterator over the interleaved frames of a file, returned by
+ * SndFile::frames.
+ *
+ * The iterator yields owned frames, next_frame borrows its internal buffer
+ * instead.
+ */
+pub struct Frames<'a, M : Readable, T : Sample> {
+    chunks : Chunks<'a, M, T>,
+    len : usize,
+    pos : usize
+}
+
+impl<'a, M : Readable, T : Sample> Frames<'a, M, T> {
+    /**
+     * Get the next frame from the internal buffer, reading the next chunk
+     * when the buffer is exhausted.
+     *
+     * Return Ok() containing one sample per channel, None at the end of the
+     * file, the error otherwise.
+     */
+    pub fn next_frame(&mut self) -> SndFileResult<Option<&[T]>> {
+        if self.pos == self.len {
+            self.len = match self.chunks.next_chunk()? {
+                Some(chunk) => chunk.len(),
+                None => return Ok(None)
+            };
+            self.pos = 0;
+        }
+        let start = self.pos;
+        self.pos += self.chunks.file.channels();
+        Ok(Some(&self.chunks.buffer[start..self.pos]))
+    }
+}
+
+impl<'a, M : Readable, T : Sample> Iterator for Frames<'a, M, T> {
+    type Item = SndFileResult<Vec<T>>;
+
+    fn next(&mut self) -> Option<SndFileResult<Vec<T>>> {
+        match self.next_frame() {
+            Ok(Some(frame)) => Some(Ok(frame.to_vec())),
+            Ok(None) => None,
+            Err(err) => Some(Err(err))
+        }
+    }
+}
+
+impl<M : Readable> SndFile<M> {
+    /**
+     * Iterate over the remaining frames of the file.
+     *
+     * Each frame holds one sample per channel. The frames are read in
+     * chunks through an internal buffer, which Frames::next_frame borrows
+     * from without allocating.
+     */
+    pub fn frames<T : Sample>(&mut self) -> Frames<'_, M, T> {
+        Frames {
+            chunks : Chunks::new(self, FRAMES_BUFFER),
+            len : 0,
+            pos : 0
+        }
+    }
+
+    /**
+     * Iterate over the remaining frames of the file, in chunks.
+     *
+     * # Argument
+     * * `frames_per_chunk` - The number of frames of each chunk but the last
+     *
+     * Return an iterator over the interleaved chunks.
+     */
+    pub fn chunks<T : Sample>(&mut self, frames_per_chunk : usize) -> Chunks<'_, M, T> {
+        Chunks::new(self, frames_per_chunk)
+    }
+}
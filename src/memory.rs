@@ -0,0 +1,136 @@
+// The MIT License (MIT)
+//
+// Copyright (c) 2013 Jeremy Letang (letang.jeremy@gmail.com)
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+//! One-call helpers to decode and encode audio held in memory.
+
+use std::cell::RefCell;
+use std::io::{self, Cursor, Seek, SeekFrom, Write};
+use std::mem;
+use std::rc::Rc;
+
+use {ErrorKind, SndFile, SndFileError, SndFileResult, SndInfo};
+
+/// Number of frames decoded at once by decode_bytes.
+const CHUNK_FRAMES : usize = 4096;
+
+/// An in-memory stream which can still be reached once the SndFile owning
+/// one of its clones is closed.
+#[derive(Clone, Default)]
+struct SharedCursor(Rc<RefCell<Cursor<Vec<u8>>>>);
+
+impl SharedCursor {
+    fn take(&self) -> Vec<u8> {
+        mem::take(self.0.borrow_mut().get_mut())
+    }
+}
+
+impl Write for SharedCursor {
+    fn write(&mut self, buf : &[u8]) -> io::Result<usize> {
+        self.0.borrow_mut().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Seek for SharedCursor {
+    fn seek(&mut self, pos : SeekFrom) -> io::Result<u64> {
+        self.0.borrow_mut().seek(pos)
+    }
+}
+
+/**
+ * Decode a whole sound file held in memory.
+ *
+ * # Argument
+ * * `bytes` - The content of the sound file
+ *
+ * Return Ok() containing the SndInfo of the file and its interleaved samples
+ * on success, the error otherwise.
+ */
+pub fn decode_bytes(bytes : &[u8]) -> SndFileResult<(SndInfo, Vec<f32>)> {
+    let mut file = SndFile::from_reader(Cursor::new(bytes.to_vec()))?;
+    let info = file.get_sndinfo();
+    let mut samples = Vec::with_capacity(file.samples_hint());
+    let mut chunks = file.chunks::<f32>(CHUNK_FRAMES);
+    while let Some(chunk) = chunks.next_chunk()? {
+        samples.extend_from_slice(chunk);
+    }
+    file.close()?;
+    Ok((info, samples))
+}
+
+/**
+ * Encode interleaved samples to a sound file held in memory.
+ *
+ * # Arguments
+ * * `info` - The format, sample rate and channels of the sound file
+ * * `samples` - The interleaved samples to encode
+ *
+ * Return Ok() containing the content of the sound file on success, the error
+ * otherwise.
+ */
+pub fn encode_to_vec(info : &SndInfo, samples : &[f32]) -> SndFileResult<Vec<u8>> {
+    let buffer = SharedCursor::default();
+    let mut file = SndFile::from_writer(buffer.clone(), *info)?;
+    if file.write_f32(samples)? != samples.len() {
+        return Err(SndFileError::new(ErrorKind::SystemError).during("write"));
+    }
+    file.close()?;
+    Ok(buffer.take())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use formats::{Format, MajorFormat, SubtypeFormat};
+
+    #[test]
+    fn encode_decode_round_trip() {
+        // Multiples of 1/128 are exact in 16 bit PCM.
+        let samples : Vec<f32> = (0..200).map(|i| (i as f32 - 100.0) / 128.0).collect();
+        for &subtype in &[SubtypeFormat::Pcm16, SubtypeFormat::Float] {
+            let mut info = SndInfo { samplerate : 8000, channels : 2, ..SndInfo::default() };
+            info.set_format(Format::new(MajorFormat::Wav, subtype));
+
+            let bytes = encode_to_vec(&info, &samples).unwrap();
+            let (decoded_info, decoded) = decode_bytes(&bytes).unwrap();
+            assert_eq!(decoded_info.format().major(), MajorFormat::Wav);
+            assert_eq!(decoded_info.format().subtype(), subtype);
+            assert_eq!(decoded_info.samplerate, 8000);
+            assert_eq!(decoded_info.channels, 2);
+            assert_eq!(decoded_info.frames, 100);
+            assert_eq!(decoded, samples);
+        }
+    }
+
+    #[test]
+    fn decode_rejects_garbage() {
+        assert!(decode_bytes(b"this is not a sound file").is_err());
+    }
+
+    #[test]
+    fn encode_rejects_invalid_format() {
+        let info = SndInfo { samplerate : 8000, channels : 1, ..SndInfo::default() };
+        assert!(encode_to_vec(&info, &[0.0]).is_err());
+    }
+}
@@ -0,0 +1,135 @@
+// The MIT License (MIT)
+//
+// Copyright (c) 2013 Jeremy Letang (letang.jeremy@gmail.com)
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+//! Snapshot of all the tags of a file.
+
+use mode::{Mode, Writable};
+use {SndFile, SndFileResult, StringSoundType};
+
+/// All the tags of a file, one field per StringSoundType
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct Metadata {
+    /// The title of the audio content
+    pub title : Option<String>,
+    /// The copyright of the audio content
+    pub copyright : Option<String>,
+    /// The software name used to create the audio content
+    pub software : Option<String>,
+    /// The artist of the audio content
+    pub artist : Option<String>,
+    /// The comment on the audio file
+    pub comment : Option<String>,
+    /// The date of creation
+    pub date : Option<String>,
+    /// The name of the album
+    pub album : Option<String>,
+    /// The licence of the content
+    pub license : Option<String>,
+    /// The track number of the audio content in an album
+    pub track_number : Option<String>,
+    /// The genre of the audio content
+    pub genre : Option<String>
+}
+
+impl Metadata {
+    /// Get the tag of the given type.
+    pub fn get(&self, string_type : StringSoundType) -> Option<&str> {
+        self.field(string_type).as_ref().map(|string| &string[..])
+    }
+
+    /// Set or clear the tag of the given type.
+    pub fn set(&mut self, string_type : StringSoundType, string : Option<String>) {
+        *self.field_mut(string_type) = string;
+    }
+
+    /// Iterate over the tags which are set, in StringSoundType::all() order.
+    pub fn iter(&self) -> impl Iterator<Item = (StringSoundType, &str)> {
+        StringSoundType::all()
+            .filter_map(move |string_type| {
+                self.get(string_type).map(|string| (string_type, string))
+            })
+    }
+
+    /// Return true if no tag is set.
+    pub fn is_empty(&self) -> bool {
+        self.iter().next().is_none()
+    }
+
+    fn field(&self, string_type : StringSoundType) -> &Option<String> {
+        match string_type {
+            StringSoundType::Title => &self.title,
+            StringSoundType::Copyright => &self.copyright,
+            StringSoundType::Software => &self.software,
+            StringSoundType::Artist => &self.artist,
+            StringSoundType::Comment => &self.comment,
+            StringSoundType::Date => &self.date,
+            StringSoundType::Album => &self.album,
+            StringSoundType::License => &self.license,
+            StringSoundType::TrackNumber => &self.track_number,
+            StringSoundType::Genre => &self.genre
+        }
+    }
+
+    fn field_mut(&mut self, string_type : StringSoundType) -> &mut Option<String> {
+        match string_type {
+            StringSoundType::Title => &mut self.title,
+            StringSoundType::Copyright => &mut self.copyright,
+            StringSoundType::Software => &mut self.software,
+            StringSoundType::Artist => &mut self.artist,
+            StringSoundType::Comment => &mut self.comment,
+            StringSoundType::Date => &mut self.date,
+            StringSoundType::Album => &mut self.album,
+            StringSoundType::License => &mut self.license,
+            StringSoundType::TrackNumber => &mut self.track_number,
+            StringSoundType::Genre => &mut self.genre
+        }
+    }
+}
+
+impl<M : Mode> SndFile<M> {
+    /// Read all the tags of the file at once.
+    pub fn metadata(&self) -> Metadata {
+        let mut metadata = Metadata::default();
+        for string_type in StringSoundType::all() {
+            metadata.set(string_type, self.get_string(string_type));
+        }
+        metadata
+    }
+}
+
+impl<M : Writable> SndFile<M> {
+    /**
+     * Write all the tags set in a Metadata to the file.
+     *
+     * The tags which are not set are left untouched.
+     *
+     * # Argument
+     * * `metadata` - The tags to write
+     *
+     * Return () on success, the first error otherwise.
+     */
+    pub fn set_metadata(&mut self, metadata : &Metadata) -> SndFileResult<()> {
+        for (string_type, string) in metadata.iter() {
+            self.set_string(string_type, string)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,78 @@
+// The MIT License (MIT)
+//
+// Copyright (c) 2013 Jeremy Letang (letang.jeremy@gmail.com)
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+//! Types for the mode a SndFile is opened with.
+//!
+//! These types are only used as the type parameter of SndFile, to make the
+//! reading and writing methods available only in the modes supporting them.
+
+use std::fmt::Debug;
+
+use OpenMode;
+
+mod private {
+    pub trait Sealed {}
+}
+
+/// A mode a SndFile can be opened with
+pub trait Mode : private::Sealed + Debug {
+    /// The mode given to libsndfile when opening the file.
+    fn open_mode() -> OpenMode;
+}
+
+/// A mode allowing to read from the file
+pub trait Readable : Mode {}
+
+/// A mode allowing to write to the file
+pub trait Writable : Mode {}
+
+/// Read only mode
+#[derive(Debug)]
+pub enum Read {}
+
+/// Write only mode
+#[derive(Debug)]
+pub enum Write {}
+
+/// Read and Write mode
+#[derive(Debug)]
+pub enum ReadWrite {}
+
+impl private::Sealed for Read {}
+impl private::Sealed for Write {}
+impl private::Sealed for ReadWrite {}
+
+impl Mode for Read {
+    fn open_mode() -> OpenMode { OpenMode::Read }
+}
+
+impl Mode for Write {
+    fn open_mode() -> OpenMode { OpenMode::Write }
+}
+
+impl Mode for ReadWrite {
+    fn open_mode() -> OpenMode { OpenMode::ReadWrite }
+}
+
+impl Readable for Read {}
+impl Readable for ReadWrite {}
+impl Writable for Write {}
+impl Writable for ReadWrite {}
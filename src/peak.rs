@@ -0,0 +1,150 @@
+// The MIT License (MIT)
+//
+// Copyright (c) 2013 Jeremy Letang (letang.jeremy@gmail.com)
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+//! Peak amplitude of the signal, read from the PEAK chunk of the file when it
+//! has one, computed by reading the whole file otherwise.
+
+use ffi;
+use formats::SubtypeFormat;
+use mode::{Readable, Writable};
+use {ErrorKind, SndFile, SndFileError, SndFileResult};
+
+impl<M : Readable> SndFile<M> {
+    /// Whether the values of the PEAK chunk are in the unit of the raw
+    /// samples, which is only the case for float data.
+    fn raw_peak_chunk(&self) -> bool {
+        matches!(self.get_sndinfo().format().subtype(),
+                 SubtypeFormat::Float | SubtypeFormat::Double)
+    }
+
+    fn signal_max_with(&mut self,
+                       use_peak_chunk : bool,
+                       calc : ffi::SF_COMMAND,
+                       operation : &'static str) -> SndFileResult<f64> {
+        let mut max = 0.0f64;
+        if use_peak_chunk && self.command(ffi::SFC_GET_SIGNAL_MAX, &mut max) == ffi::SF_TRUE {
+            return Ok(max);
+        }
+        match SndFileError::from_code(self.command(calc, &mut max)) {
+            None => Ok(max),
+            Some(err) => Err(err.during(operation).on(&self.origin))
+        }
+    }
+
+    fn max_all_channels_with(&mut self,
+                             use_peak_chunk : bool,
+                             calc : ffi::SF_COMMAND,
+                             operation : &'static str) -> SndFileResult<Vec<f64>> {
+        let mut max = vec![0.0f64; self.channels()];
+        if use_peak_chunk
+            && self.command_buffer(ffi::SFC_GET_MAX_ALL_CHANNELS, &mut max) == ffi::SF_TRUE {
+            return Ok(max);
+        }
+        match SndFileError::from_code(self.command_buffer(calc, &mut max)) {
+            None => Ok(max),
+            Some(err) => Err(err.during(operation).on(&self.origin))
+        }
+    }
+
+    /**
+     * Get the peak absolute sample value of the file, in the unit of its
+     * samples.
+     *
+     * The PEAK chunk is used for float data, otherwise the whole file is read
+     * and the read position restored.
+     */
+    pub fn signal_max(&mut self) -> SndFileResult<f64> {
+        let use_peak_chunk = self.raw_peak_chunk();
+        self.signal_max_with(use_peak_chunk, ffi::SFC_CALC_SIGNAL_MAX, "SFC_CALC_SIGNAL_MAX")
+    }
+
+    /**
+     * Get the peak absolute sample value of the file, normalized to
+     * [0.0, 1.0].
+     *
+     * The PEAK chunk is used when present, otherwise the whole file is read
+     * and the read position restored.
+     */
+    pub fn normalized_signal_max(&mut self) -> SndFileResult<f64> {
+        self.signal_max_with(true, ffi::SFC_CALC_NORM_SIGNAL_MAX, "SFC_CALC_NORM_SIGNAL_MAX")
+    }
+
+    /**
+     * Get the peak absolute sample value of each channel, in the unit of the
+     * samples.
+     *
+     * The PEAK chunk is used for float data, otherwise the whole file is read
+     * and the read position restored.
+     */
+    pub fn max_all_channels(&mut self) -> SndFileResult<Vec<f64>> {
+        let use_peak_chunk = self.raw_peak_chunk();
+        self.max_all_channels_with(use_peak_chunk,
+                                   ffi::SFC_CALC_MAX_ALL_CHANNELS,
+                                   "SFC_CALC_MAX_ALL_CHANNELS")
+    }
+
+    /**
+     * Get the peak absolute sample value of each channel, normalized to
+     * [0.0, 1.0].
+     *
+     * The PEAK chunk is used when present, otherwise the whole file is read
+     * and the read position restored.
+     */
+    pub fn normalized_max_all_channels(&mut self) -> SndFileResult<Vec<f64>> {
+        self.max_all_channels_with(true,
+                                   ffi::SFC_CALC_NORM_MAX_ALL_CHANNELS,
+                                   "SFC_CALC_NORM_MAX_ALL_CHANNELS")
+    }
+}
+
+impl<M : Writable> SndFile<M> {
+    /**
+     * Set whether a PEAK chunk is added to the file.
+     *
+     * libsndfile adds one by default to WAV, AIFF, CAF and RF64 float
+     * files, and never to other files, so that not adding one to them
+     * succeeds. This must be done before writing any audio data.
+     *
+     * Return () on success, UnsupportedCommand if a PEAK chunk cannot be
+     * added to the file, the error of libsndfile if audio data was already
+     * written.
+     */
+    pub fn set_add_peak_chunk(&mut self, add : bool) -> SndFileResult<()> {
+        // libsndfile returns the value it was given on success, which cannot
+        // be told apart from a failure when removing the chunk, so the
+        // failures are found from the format and the error of the handle.
+        if !self.info.format().supports_peak_chunk() {
+            return if add {
+                Err(SndFileError::new(ErrorKind::UnsupportedCommand)
+                    .during("SFC_SET_ADD_PEAK_CHUNK")
+                    .on(&self.origin))
+            } else {
+                Ok(())
+            };
+        }
+        let value = if add { ffi::SF_TRUE } else { ffi::SF_FALSE };
+        self.command_value(ffi::SFC_SET_ADD_PEAK_CHUNK, value);
+        match self.error() {
+            Some(err) => Err(err.during("SFC_SET_ADD_PEAK_CHUNK")),
+            None => Ok(())
+        }
+    }
+}
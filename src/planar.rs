@@ -0,0 +1,133 @@
+// The MIT License (MIT)
+//
+// Copyright (c) 2013 Jeremy Letang (letang.jeremy@gmail.com)
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+//! Planar access to the samples, with one buffer per channel.
+//!
+//! libsndfile only reads and writes interleaved frames, so the samples go
+//! through a scratch buffer holding a limited number of frames.
+
+use mode::{Mode, Readable, Writable};
+use {ErrorKind, Sample, SndFile, SndFileError, SndFileResult};
+
+/// Number of frames interleaved or deinterleaved at once.
+const SCRATCH_FRAMES : usize = 1024;
+
+impl<M : Mode> SndFile<M> {
+    /// Check that there is one buffer per channel and that all of them have
+    /// the same length, which is returned.
+    fn planar_len<I>(&self, lens : I, operation : &'static str) -> SndFileResult<usize>
+        where I : ExactSizeIterator<Item = usize> {
+        let count = lens.len();
+        let mut len = None;
+        let equal = lens.into_iter().all(|l| *len.get_or_insert(l) == l);
+        if count == self.channels() && equal {
+            Ok(len.unwrap_or(0))
+        } else {
+            Err(SndFileError::new(ErrorKind::InvalidBufferLength)
+                .during(operation)
+                .on(&self.origin))
+        }
+    }
+}
+
+impl<M : Readable> SndFile<M> {
+    /**
+     * Read frames into one buffer per channel.
+     *
+     * # Argument
+     * * `channels` - The buffers to fill, one per channel of the file and
+     *   all of the same length.
+     *
+     * Return Ok() containing the count of frames read, the error otherwise.
+     */
+    pub fn read_planar<T : Sample>(&mut self, channels : &mut [&mut [T]]) -> SndFileResult<usize> {
+        let frames = self.planar_len(channels.iter().map(|c| c.len()), "read_planar")?;
+        let mut scratch = vec![T::default(); frames.min(SCRATCH_FRAMES) * self.channels()];
+        let mut done = 0;
+        while done < frames {
+            let wanted = (frames - done).min(SCRATCH_FRAMES);
+            let read = self.read_frames(&mut scratch[..wanted * channels.len()])?;
+            for (i, frame) in scratch[..read * channels.len()].chunks(channels.len()).enumerate() {
+                for (channel, &sample) in channels.iter_mut().zip(frame) {
+                    channel[done + i] = sample;
+                }
+            }
+            done += read;
+            if read < wanted {
+                break;
+            }
+        }
+        Ok(done)
+    }
+
+    /**
+     * Read all the remaining frames, one Vec per channel.
+     *
+     * Return Ok() containing the samples of each channel, the error
+     * otherwise.
+     */
+    pub fn read_all_planar<T : Sample>(&mut self) -> SndFileResult<Vec<Vec<T>>> {
+        let frames = self.samples_hint() / self.channels();
+        let mut channels : Vec<Vec<T>> = (0..self.channels())
+            .map(|_| Vec::with_capacity(frames))
+            .collect();
+        let mut chunks = self.chunks::<T>(SCRATCH_FRAMES);
+        while let Some(chunk) = chunks.next_chunk()? {
+            for frame in chunk.chunks(channels.len()) {
+                for (channel, &sample) in channels.iter_mut().zip(frame) {
+                    channel.push(sample);
+                }
+            }
+        }
+        Ok(channels)
+    }
+}
+
+impl<M : Writable> SndFile<M> {
+    /**
+     * Write frames from one buffer per channel.
+     *
+     * # Argument
+     * * `channels` - The samples to write, one buffer per channel of the
+     *   file and all of the same length.
+     *
+     * Return Ok() containing the count of written frames, the error
+     * otherwise.
+     */
+    pub fn write_planar<T : Sample>(&mut self, channels : &[&[T]]) -> SndFileResult<usize> {
+        let frames = self.planar_len(channels.iter().map(|c| c.len()), "write_planar")?;
+        let mut scratch = Vec::with_capacity(frames.min(SCRATCH_FRAMES) * self.channels());
+        let mut done = 0;
+        while done < frames {
+            let wanted = (frames - done).min(SCRATCH_FRAMES);
+            scratch.clear();
+            for i in done..done + wanted {
+                scratch.extend(channels.iter().map(|channel| channel[i]));
+            }
+            let written = self.write_frames(&scratch)?;
+            done += written;
+            if written < wanted {
+                break;
+            }
+        }
+        Ok(done)
+    }
+}
@@ -0,0 +1,145 @@
+// The MIT License (MIT)
+//
+// Copyright (c) 2013 Jeremy Letang (letang.jeremy@gmail.com)
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+//! Raw access to the encoded audio data, without decoding or encoding.
+
+use libc::c_void;
+
+use ffi;
+use formats::{MajorFormat, SubtypeFormat};
+use mode::{Mode, Readable, Writable};
+use {ErrorKind, SndFile, SndFileError, SndFileResult};
+
+/// Location of a sound file embedded in a larger file
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct EmbedFileInfo {
+    /// Offset of the sound file in the file, in bytes
+    pub offset : i64,
+    /// Length of the sound file, in bytes
+    pub length : i64
+}
+
+impl<M : Mode> SndFile<M> {
+    /**
+     * Get the size in bytes of the blocks read_raw and write_raw work on.
+     *
+     * This is a whole frame for PCM, float and companded data, a block for
+     * GSM 6.10.
+     *
+     * IMA and MS ADPCM are not supported: their block size is stored in the
+     * header of the file and libsndfile does not expose it. Like compressed
+     * formats, they return None, and read_raw and write_raw reject them.
+     */
+    pub fn raw_block_size(&self) -> Option<usize> {
+        let format = self.info.format();
+        let width = match format.subtype() {
+            SubtypeFormat::PcmS8
+                | SubtypeFormat::PcmU8
+                | SubtypeFormat::Ulaw
+                | SubtypeFormat::Alaw
+                | SubtypeFormat::Dpcm8 => 1,
+            SubtypeFormat::Pcm16 | SubtypeFormat::Dpcm16 => 2,
+            SubtypeFormat::Pcm24 => 3,
+            SubtypeFormat::Pcm32 | SubtypeFormat::Float => 4,
+            SubtypeFormat::Double => 8,
+            SubtypeFormat::Gsm610 => return match format.major() {
+                MajorFormat::Wav | MajorFormat::W64 | MajorFormat::Wavex => Some(65),
+                _ => Some(33)
+            },
+            _ => return None
+        };
+        Some(width * self.channels())
+    }
+
+    /// Whether the raw data is in the opposite endian-ness of the CPU.
+    pub fn raw_data_needs_endswap(&self) -> bool {
+        self.command_value(ffi::SFC_RAW_DATA_NEEDS_ENDSWAP, 0) == ffi::SF_TRUE
+    }
+
+    /**
+     * Get the location of the sound file in the file it was opened from.
+     *
+     * This is the window of an embedded sound file, and offset 0 with the
+     * whole length for ordinary files. It does not locate the audio data
+     * after the header: libsndfile exposes no query for that offset.
+     */
+    pub fn embed_file_info(&self) -> Option<EmbedFileInfo> {
+        let mut info = ffi::EmbedFileInfo { offset : 0, length : 0 };
+        match self.command(ffi::SFC_GET_EMBED_FILE_INFO, &mut info) {
+            0 => Some(EmbedFileInfo { offset : info.offset, length : info.length }),
+            _ => None
+        }
+    }
+
+    /// Check that a raw buffer of `len` bytes holds whole blocks.
+    fn check_raw_len(&self, len : usize, operation : &'static str) -> SndFileResult<()> {
+        let kind = match self.raw_block_size() {
+            None => ErrorKind::UnsupportedEncoding,
+            Some(size) if !len.is_multiple_of(size) => ErrorKind::InvalidBufferLength,
+            Some(_) => return Ok(())
+        };
+        Err(SndFileError::new(kind)
+            .during(operation)
+            .on(&self.origin))
+    }
+}
+
+impl<M : Readable> SndFile<M> {
+    /**
+     * Read the encoded audio data as is.
+     *
+     * # Argument
+     * * `bytes` - The buffer to fill, its length must be a multiple of
+     *   raw_block_size().
+     *
+     * Return Ok() containing the count of bytes read, UnsupportedEncoding if
+     * the block size of the encoding is unknown, an other error otherwise.
+     */
+    pub fn read_raw(&mut self, bytes : &mut [u8]) -> SndFileResult<usize> {
+        self.check_raw_len(bytes.len(), "read_raw")?;
+        self.clear_stream_error();
+        let read = unsafe {
+            ffi::sf_read_raw(self.handle, bytes.as_mut_ptr() as *mut c_void, bytes.len() as i64)
+        };
+        self.check_count(bytes.len(), read, "read_raw")
+    }
+}
+
+impl<M : Writable> SndFile<M> {
+    /**
+     * Write already encoded audio data as is.
+     *
+     * # Argument
+     * * `bytes` - The data to write, its length must be a multiple of
+     *   raw_block_size().
+     *
+     * Return Ok() containing the count of written bytes, UnsupportedEncoding
+     * if the block size of the encoding is unknown, an other error otherwise.
+     */
+    pub fn write_raw(&mut self, bytes : &[u8]) -> SndFileResult<usize> {
+        self.check_raw_len(bytes.len(), "write_raw")?;
+        self.clear_stream_error();
+        let written = unsafe {
+            ffi::sf_write_raw(self.handle, bytes.as_ptr() as *const c_void, bytes.len() as i64)
+        };
+        self.check_count(bytes.len(), written, "write_raw")
+    }
+}
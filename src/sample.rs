@@ -0,0 +1,71 @@
+// The MIT License (MIT)
+//
+// Copyright (c) 2013 Jeremy Letang (letang.jeremy@gmail.com)
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+//! The types of samples libsndfile can read and write.
+
+use ffi;
+
+pub mod private {
+    use ffi;
+
+    /// Dispatch to the libsndfile function handling the sample type. Not
+    /// reachable outside of the crate, so Sample cannot be implemented for
+    /// other types.
+    pub trait Sealed : Sized {
+        unsafe fn sf_read(sndfile : *mut ffi::SNDFILE, ptr : *mut Self, items : i64) -> i64;
+        unsafe fn sf_readf(sndfile : *mut ffi::SNDFILE, ptr : *mut Self, frames : i64) -> i64;
+        unsafe fn sf_write(sndfile : *mut ffi::SNDFILE, ptr : *const Self, items : i64) -> i64;
+        unsafe fn sf_writef(sndfile : *mut ffi::SNDFILE, ptr : *const Self, frames : i64) -> i64;
+    }
+}
+
+/// A type of sample libsndfile can convert the audio data to and from:
+/// i16, i32, f32 and f64.
+pub trait Sample : private::Sealed + Copy + Default + PartialOrd + 'static {}
+
+macro_rules! impl_sample {
+    ($t:ty, $read:ident, $readf:ident, $write:ident, $writef:ident) => {
+        impl private::Sealed for $t {
+            unsafe fn sf_read(sndfile : *mut ffi::SNDFILE, ptr : *mut $t, items : i64) -> i64 {
+                ffi::$read(sndfile, ptr, items)
+            }
+
+            unsafe fn sf_readf(sndfile : *mut ffi::SNDFILE, ptr : *mut $t, frames : i64) -> i64 {
+                ffi::$readf(sndfile, ptr, frames)
+            }
+
+            unsafe fn sf_write(sndfile : *mut ffi::SNDFILE, ptr : *const $t, items : i64) -> i64 {
+                ffi::$write(sndfile, ptr, items)
+            }
+
+            unsafe fn sf_writef(sndfile : *mut ffi::SNDFILE, ptr : *const $t, frames : i64) -> i64 {
+                ffi::$writef(sndfile, ptr, frames)
+            }
+        }
+
+        impl Sample for $t {}
+    }
+}
+
+impl_sample!(i16, sf_read_short, sf_readf_short, sf_write_short, sf_writef_short);
+impl_sample!(i32, sf_read_int, sf_readf_int, sf_write_int, sf_writef_int);
+impl_sample!(f32, sf_read_float, sf_readf_float, sf_write_float, sf_writef_float);
+impl_sample!(f64, sf_read_double, sf_readf_double, sf_write_double, sf_writef_double);
@@ -59,7 +59,11 @@ rustpkg build sndfile
 
 extern crate libc;
 
-use std::path::Path;
+use std::fmt;
+use std::io::{self, SeekFrom};
+use std::marker::PhantomData;
+use std::mem;
+use std::path::{Path, PathBuf};
 use std::ptr;
 use std::ffi::{CStr, CString};
 
@@ -67,18 +71,56 @@ use std::ffi::{CStr, CString};
 mod libsndfile {
     #[cfg(any(target_os="macos", target_os="linux"))]
     #[link(name = "sndfile")]
-    extern {}
+    extern "C" {}
 
     #[cfg(windows)]
     #[link(name = "sndfile-1")]
-    extern {}
+    extern "C" {}
 }
 
+pub use audio_buffer::{read_file, write_file, AudioBuffer};
+pub use broadcast::BroadcastInfo;
+pub use builder::WriterBuilder;
+pub use cue::CuePoint;
+pub use encoder::BitrateMode;
+pub use instrument::{Instrument, InstrumentLoop, LoopInfo, LoopMode};
+pub use iter::{Chunks, Frames};
+pub use error::{ErrorKind, SndFileError, SndFileResult};
+pub use formats::{Endian, Format, MajorFormat, SubtypeFormat};
+pub use memory::{decode_bytes, encode_to_vec};
+pub use metadata::Metadata;
+pub use raw::EmbedFileInfo;
+pub use sample::Sample;
+
+pub mod formats;
+pub mod mode;
+mod audio_buffer;
+mod broadcast;
+mod builder;
+mod conversion;
+mod cue;
+mod encoder;
+mod error;
 mod ffi;
+mod instrument;
+mod iter;
+mod memory;
+mod metadata;
+mod peak;
+mod planar;
+mod raw;
+mod sample;
+mod virtual_io;
+
+use mode::{Mode, Readable, Writable};
+
+/// Largest number of samples preallocated from the header of a file, 64 MiB
+/// of f32.
+const MAX_SAMPLES_HINT : usize = 1 << 24;
 
 /// The SndInfo structure is for passing data between the calling
 /// function and the library when opening a file for reading or writing.
-#[derive(Clone, PartialEq, PartialOrd, Debug, Copy)]
+#[derive(Clone, PartialEq, PartialOrd, Debug, Copy, Default)]
 #[repr(C)]
 pub struct SndInfo {
     /// The number of frames
@@ -87,7 +129,7 @@ pub struct SndInfo {
     pub samplerate : i32,
     /// The number of channels
     pub channels : i32,
-    /// The format from enum FormatType
+    /// The raw format, see the format() method for its typed form
     pub format : i32,
     /// The sections
     pub sections : i32,
@@ -95,6 +137,18 @@ pub struct SndInfo {
     pub seekable : i32
 }
 
+impl SndInfo {
+    /// Get the typed format of the file.
+    pub fn format(&self) -> Format {
+        Format::from_raw(self.format)
+    }
+
+    /// Set the format of the file.
+    pub fn set_format(&mut self, format : Format) {
+        self.format = format.to_raw();
+    }
+}
+
 /// Modes availables for the open function.
 #[derive(Clone, PartialEq, PartialOrd, Debug, Copy)]
 pub enum OpenMode {
@@ -131,251 +185,154 @@ pub enum StringSoundType {
     Genre       = ffi::SF_STR_GENRE as isize
 }
 
-/// Types of error who can be return by API functions
-#[derive(Clone, PartialEq, PartialOrd, Debug, Copy)]
-pub enum SndFileError {
-    /// The file format is not recognized
-    UnrecognisedFormat,
-    /// There is an internal system error
-    SystemError,
-    /// The file is malformed
-    MalformedFile,
-    /// The encoding of the file is not supported by sndfile
-    UnsupportedEncoding,
-    /// Any internal error code
-    InternalError(i32)
+impl StringSoundType {
+    /// Iterate over all the types of string.
+    pub fn all() -> impl Iterator<Item = StringSoundType> {
+        [StringSoundType::Title,
+         StringSoundType::Copyright,
+         StringSoundType::Software,
+         StringSoundType::Artist,
+         StringSoundType::Comment,
+         StringSoundType::Date,
+         StringSoundType::Album,
+         StringSoundType::License,
+         StringSoundType::TrackNumber,
+         StringSoundType::Genre].iter().cloned()
+    }
 }
 
-impl SndFileError {
-    /// Get a string representation of the error, as returned by libsndfile's
-    /// sf_error_number
-    pub fn desc(&self) -> String {
-        let error_code = match *self {
-            SndFileError::UnrecognisedFormat => ffi::SF_ERR_UNRECOGNISED_FORMAT,
-            SndFileError::SystemError => ffi::SF_ERR_SYSTEM,
-            SndFileError::MalformedFile => ffi::SF_ERR_MALFORMED_FILE,
-            SndFileError::UnsupportedEncoding => ffi::SF_ERR_UNSUPPORTED_ENCODING,
-            SndFileError::InternalError(err) => err
-        };
-        unsafe {
-            std::str::from_utf8_unchecked(CStr::from_ptr(
-                ffi::sf_error_number(error_code)).to_bytes()).to_string()
-        }
-    }
+/// SndFile object, used to load/store sound from a file path or an fd.
+///
+/// The SndFile owns its libsndfile handle, which is closed when the object is
+/// dropped. Use `close` to observe the error returned by libsndfile on
+/// closing, and `reopen` to get a second, independent handle on the same file.
+///
+/// The type parameter is the mode the file is opened with, one of the types
+/// of the `mode` module: reading methods are only available on readable
+/// files and writing methods on writable ones.
+#[derive(Debug)]
+#[allow(missing_copy_implementations)]
+pub struct SndFile<M> {
+    handle : *mut ffi::SNDFILE,
+    info : SndInfo,
+    origin : Origin,
+    // The stream handed to libsndfile by virtual I/O SndFiles, null otherwise.
+    // It comes from Box::into_raw and is freed on drop, after sf_close.
+    stream : *mut virtual_io::VirtualStream,
+    mode : PhantomData<M>
+}
 
-    fn from_code(code: i32) -> Option<SndFileError> {
-        match code {
-            ffi::SF_ERR_NO_ERROR => None,
-            ffi::SF_ERR_UNRECOGNISED_FORMAT => Some(SndFileError::UnrecognisedFormat),
-            ffi::SF_ERR_SYSTEM => Some(SndFileError::SystemError),
-            ffi::SF_ERR_MALFORMED_FILE => Some(SndFileError::MalformedFile),
-            ffi::SF_ERR_UNSUPPORTED_ENCODING => Some(SndFileError::UnsupportedEncoding),
-            _ => Some(SndFileError::InternalError(code))
-        }
-    }
+/// Where the data of a SndFile comes from, used to reopen it and to report
+/// errors.
+#[derive(Clone, Debug)]
+enum Origin {
+    Path(PathBuf),
+    Fd(i32),
+    Stream
+}
 
-    fn code_to_result<T>(code: i32, ok: T) -> SndFileResult<T> {
-        match SndFileError::from_code(code) {
-            Some(err) => Err(err),
-            None => Ok(ok)
+impl fmt::Display for Origin {
+    fn fmt(&self, f : &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Origin::Path(ref path) => write!(f, "{}", path.display()),
+            Origin::Fd(fd) => write!(f, "fd {}", fd),
+            Origin::Stream => write!(f, "virtual I/O stream")
         }
     }
 }
 
-/// Type alias for a Result with SndFileError
-pub type SndFileResult<T> = Result<T, SndFileError>;
+/// Call sf_open with the bytes of the path on Unix, which may not be UTF-8.
+///
+/// Return None if the path contains a NUL byte. The C path lives until
+/// sf_open returns.
+#[cfg(unix)]
+fn sf_open_path(path : &Path, mode : OpenMode, info : &mut SndInfo) -> Option<*mut ffi::SNDFILE> {
+    use std::os::unix::ffi::OsStrExt;
 
-/// Enum to set the offset with method seek
-#[derive(Clone, PartialEq, PartialOrd, Debug, Copy)]
-pub enum SeekMode {
-    /// The offset is set to the start of the audio data plus offset (multichannel) frames.
-    SeekSet = ffi::SEEK_SET as isize,
-    /// The offset is set to its current location plus offset (multichannel) frames.
-    SeekCur = ffi::SEEK_CUR as isize,
-    /// The offset is set to the end of the data plus offset (multichannel) frames.
-    SeekEnd = ffi::SEEK_END as isize
+    let c_path = CString::new(path.as_os_str().as_bytes()).ok()?;
+    Some(unsafe { ffi::sf_open(c_path.as_ptr(), mode as i32, info) })
 }
 
-/// Enum who contains the list of the supported audio format
-#[derive(Clone, PartialEq, PartialOrd, Debug, Copy)]
-pub enum FormatType {
-    /// Microsoft WAV format (little endian)
-    FormatWav = ffi::SF_FORMAT_WAV as isize,
-    /// Apple/SGI AIFF format (big endian)
-    FormatAiff = ffi::SF_FORMAT_AIFF as isize,
-    /// Sun/NeXT AU format (big endian)
-    FormatAu = ffi::SF_FORMAT_AU as isize,
-    /// RAW PCM data
-    FormatRaw = ffi::SF_FORMAT_RAW as isize,
-    /// Ensoniq PARIS file format
-    FormatPaf = ffi::SF_FORMAT_PAF as isize,
-    /// Amiga IFF / SVX8 / SV16 format
-    FormatSvx = ffi::SF_FORMAT_SVX as isize,
-    /// Sphere NIST format
-    FormatNist = ffi::SF_FORMAT_NIST as isize,
-    /// VOC files
-    FormatVoc = ffi::SF_FORMAT_VOC as isize,
-    /// Berkeley/IRCAM/CARL
-    FormatIrcam = ffi::SF_FORMAT_IRCAM as isize,
-    /// Sonic Foundry's 64 bit RIFF/WAV
-    FormatW64 = ffi::SF_FORMAT_W64 as isize,
-    /// Matlab (tm) V4.2 / GNU Octave 2.0
-    FormatMat4 = ffi::SF_FORMAT_MAT4 as isize,
-    /// Matlab (tm) V5.0 / GNU Octave 2.1
-    FormatMat5 = ffi::SF_FORMAT_MAT5 as isize,
-    /// Portable Voice Format
-    FormatPvf = ffi::SF_FORMAT_PVF as isize,
-    /// Fasttracker 2 Extended Instrument
-    FormatXi = ffi::SF_FORMAT_XI as isize,
-    /// HMM Tool Kit format
-    FormatHtk = ffi::SF_FORMAT_HTK as isize,
-    /// Midi Sample Dump Standard
-    FormatSds = ffi::SF_FORMAT_SDS as isize,
-    /// Audio Visual Research
-    FormatAvr = ffi::SF_FORMAT_AVR as isize,
-    /// MS WAVE with WAVEFORMATEX
-    FormatWavex = ffi::SF_FORMAT_WAVEX as isize,
-    /// Sound Designer 2
-    FormatSd2 = ffi::SF_FORMAT_SD2 as isize,
-    /// FLAC lossless file format
-    FormatFlac = ffi::SF_FORMAT_FLAC as isize,
-    /// Core Audio File format
-    FormatCaf = ffi::SF_FORMAT_CAF as isize,
-    /// Psion WVE format
-    FormatWve = ffi::SF_FORMAT_WVE as isize,
-    /// Xiph OGG container
-    FormatOgg = ffi::SF_FORMAT_OGG as isize,
-    /// Akai MPC 2000 sampler
-    FormatMpc2k = ffi::SF_FORMAT_MPC2K as isize,
-    /// RF64 WAV file
-    FormatRf64 = ffi::SF_FORMAT_RF64 as isize,
-    /// Signed 8 bit data
-    FormatPcmS8 = ffi::SF_FORMAT_PCM_S8 as isize,
-    /// Signed 16 bit data
-    FormatPcm16 = ffi::SF_FORMAT_PCM_16 as isize,
-    /// Signed 24 bit data
-    FormatPcm24 = ffi::SF_FORMAT_PCM_24 as isize,
-    /// Signed 32 bit data
-    FormatPcm32 = ffi::SF_FORMAT_PCM_32 as isize,
-    /// Unsigned 8 bit data (WAV and RAW only)
-    FormatPcmU8 = ffi::SF_FORMAT_PCM_U8 as isize,
-    /// 32 bit float data
-    FormatFloat = ffi::SF_FORMAT_FLOAT as isize,
-    /// 64 bit float data
-    FormatDouble = ffi::SF_FORMAT_DOUBLE as isize,
-    /// U-Law encoded
-    FormatUlaw = ffi::SF_FORMAT_ULAW as isize,
-    /// A-Law encoded
-    FormatAlaw = ffi::SF_FORMAT_ALAW as isize,
-    /// IMA ADPCM
-    FormatImaAdpcm = ffi::SF_FORMAT_IMA_ADPCM as isize,
-    /// Microsoft ADPCM
-    FormatApcm = ffi::SF_FORMAT_MS_ADPCM  as isize,
-    /// GSM 6.10 encoding
-    FormatGsm610 = ffi::SF_FORMAT_GSM610 as isize,
-    /// Oki Dialogic ADPCM encoding
-    FormatVoxAdpcm = ffi::SF_FORMAT_VOX_ADPCM as isize,
-    /// 32kbs G721 ADPCM encoding
-    FormatG72132 = ffi::SF_FORMAT_G721_32 as isize,
-    /// 24kbs G723 ADPCM encoding
-    FormatG72324 = ffi::SF_FORMAT_G723_24 as isize,
-    /// 40kbs G723 ADPCM encoding
-    FormatG72340 = ffi::SF_FORMAT_G723_40 as isize,
-    /// 12 bit Delta Width Variable Word encoding
-    FormatDww12 = ffi::SF_FORMAT_DWVW_12 as isize,
-    /// 16 bit Delta Width Variable Word encoding
-    FormatDww16 = ffi::SF_FORMAT_DWVW_16 as isize,
-    /// 24 bit Delta Width Variable Word encoding
-    FormatDww24 = ffi::SF_FORMAT_DWVW_24 as isize,
-    /// N bit Delta Width Variable Word encoding
-    FormatDwwN = ffi::SF_FORMAT_DWVW_N as isize,
-    /// 8 bit differential PCM (XI only)
-    FormatDpcm8 = ffi::SF_FORMAT_DPCM_8 as isize,
-    /// 16 bit differential PCM (XI only)
-    FormatDpcm16 = ffi::SF_FORMAT_DPCM_16 as isize,
-    /// Xiph Vorbis encoding
-    FormatVorbis = ffi::SF_FORMAT_VORBIS as isize,
-    /// Default file endian-ness
-    EndianFile = ffi::SF_ENDIAN_FILE as isize,
-    /// Force little endian-ness
-    EndianLittle = ffi::SF_ENDIAN_LITTLE as isize,
-    /// Force big endian-ness
-    EndianBig = ffi::SF_ENDIAN_BIG as isize,
-    /// Force CPU endian-ness
-    EndianCpu = ffi::SF_ENDIAN_CPU as isize,
-    /// Sub mask
-    FormatSubMask = ffi::SF_FORMAT_SUBMASK as isize,
-    /// Type mask
-    FormatTypeMask = ffi::SF_FORMAT_TYPEMASK as isize,
+/// Call sf_wchar_open with the UTF-16 path on Windows.
+///
+/// Return None if the path contains a NUL character.
+#[cfg(windows)]
+fn sf_open_path(path : &Path, mode : OpenMode, info : &mut SndInfo) -> Option<*mut ffi::SNDFILE> {
+    use std::os::windows::ffi::OsStrExt;
+
+    let mut w_path : Vec<u16> = path.as_os_str().encode_wide().collect();
+    if w_path.contains(&0) {
+        return None;
+    }
+    w_path.push(0);
+    Some(unsafe { ffi::sf_wchar_open(w_path.as_ptr(), mode as i32, info) })
 }
 
-/// SndFile object, used to load/store sound from a file path or an fd.
-#[derive(Debug)]
-#[allow(missing_copy_implementations)]
-pub struct SndFile {
-    handle : *mut ffi::SNDFILE,
-    info : SndInfo
+/// Call sf_open with the path, which must be valid unicode on platforms
+/// other than Unix and Windows.
+///
+/// Return None if the path is not unicode or contains a NUL byte.
+#[cfg(not(any(unix, windows)))]
+fn sf_open_path(path : &Path, mode : OpenMode, info : &mut SndInfo) -> Option<*mut ffi::SNDFILE> {
+    let c_path = CString::new(path.to_str()?).ok()?;
+    Some(unsafe { ffi::sf_open(c_path.as_ptr(), mode as i32, info) })
 }
 
-impl Clone for SndFile {
-    fn clone(&self) -> SndFile {
-        SndFile {
-            handle : self.handle,
-            info : self.info.clone()
+/// A sound file opened for reading only
+pub type SndReader = SndFile<mode::Read>;
+
+/// A sound file opened for writing only
+pub type SndWriter = SndFile<mode::Write>;
+
+/// A sound file opened for reading and writing
+pub type SndReadWriter = SndFile<mode::ReadWrite>;
+
+impl<M> Drop for SndFile<M> {
+    fn drop(&mut self) {
+        if !self.handle.is_null() {
+            unsafe { ffi::sf_close(self.handle); }
+        }
+        if !self.stream.is_null() {
+            drop(unsafe { Box::from_raw(self.stream) });
         }
     }
 }
 
-impl SndFile {
+impl<M : Readable> SndFile<M> {
     /**
-     * Construct SndFile object with the path to the music and a mode to open it.
+     * Construct SndFile object with the path to the music, opened with the
+     * mode of the SndFile type.
      *
-     * # Arguments
+     * The format is read from the file, so only readable files can be opened
+     * this way; writers are created with SndFile::create, which gives
+     * libsndfile the format of the file.
+     *
+     * # Argument
      * * `path` - The path to load the music
-     * * `mode` - The mode to open the music
      *
      * Return Ok() containing the SndFile on success, a string representation
      * of the error otherwise.
      */
-    pub fn new(path : &Path, mode : OpenMode) -> SndFileResult<SndFile> {
-        let mut info : SndInfo = SndInfo {
-            frames : 0,
-            samplerate : 0,
-            channels : 0,
-            format : 0,
-            sections : 0,
-            seekable : 0
-        };
-        let c_path = path.to_str().unwrap().to_string();
-        let cstr = CString::new(c_path).unwrap().as_ptr();
-        let tmp_sndfile = unsafe {ffi::sf_open(cstr, mode as i32, &mut info as *mut SndInfo) };
-        if tmp_sndfile.is_null() {
-            Err(SndFileError::from_code(unsafe { ffi::sf_error(ptr::null_mut())})
-                .expect("expected error from sf_error, got no error"))
-        } else {
-            Ok(SndFile {
-                handle :    tmp_sndfile,
-                info :      info
-            })
-        }
+    pub fn new<P : AsRef<Path>>(path : P) -> SndFileResult<SndFile<M>> {
+        SndFile::open(path.as_ref(), SndInfo::default())
     }
 
     /**
-     * Construct SndFile object with the fd of the file containing the music
-     * and a mode to open it.
+     * Construct SndFile object with the fd of the file containing the music,
+     * opened with the mode of the SndFile type.
+     *
+     * As with new, the format is read from the file, so only readable files
+     * can be opened this way.
      *
      * # Arguments
      * * `fd` - The fd to load the music
-     * * `mode` - The mode to open the music
      * * `close_desc` - Should SndFile close the fd at exit?
      *
      * Return Ok() containing the SndFile on success, a string representation of
      * the error otherwise.
      */
-    pub fn new_with_fd(fd : i32,
-                       mode : OpenMode,
-                       close_desc : bool) -> SndFileResult<SndFile> {
-        let info : SndInfo = SndInfo {
+    pub fn new_with_fd(fd : i32, close_desc : bool) -> SndFileResult<SndFile<M>> {
+        let mut info : SndInfo = SndInfo {
             frames : 0,
             samplerate : 0,
             channels : 0,
@@ -385,27 +342,127 @@ impl SndFile {
         };
         let tmp_sndfile = match close_desc {
             true    => unsafe {
-                ffi::sf_open_fd(fd, mode as i32, &info, ffi::SF_TRUE)
+                ffi::sf_open_fd(fd, M::open_mode() as i32, &mut info, ffi::SF_TRUE)
             },
             false   => unsafe {
-                ffi::sf_open_fd(fd, mode as i32, &info, ffi::SF_FALSE)
+                ffi::sf_open_fd(fd, M::open_mode() as i32, &mut info, ffi::SF_FALSE)
             }
         };
         if tmp_sndfile.is_null() {
-            Err(SndFileError::from_code(unsafe {
-                ffi::sf_error(ptr::null_mut())
-            }).expect("expected error from sf_error, got no error"))
+            Err(SndFileError::from_handle(ptr::null_mut())
+                .expect("expected error from sf_error, got no error")
+                .during("open")
+                .on(Origin::Fd(fd)))
+        } else {
+            Ok(SndFile {
+                handle :    tmp_sndfile,
+                info,
+                origin :    Origin::Fd(fd),
+                stream :    ptr::null_mut(),
+                mode :      PhantomData
+            })
+        }
+    }
+}
+
+impl<M : Mode> SndFile<M> {
+    fn open(path : &Path, mut info : SndInfo) -> SndFileResult<SndFile<M>> {
+        let tmp_sndfile = match sf_open_path(path, M::open_mode(), &mut info) {
+            Some(handle) => handle,
+            None => return Err(SndFileError::new(ErrorKind::InvalidPath)
+                               .during("open")
+                               .on(path.display()))
+        };
+        if tmp_sndfile.is_null() {
+            Err(SndFileError::from_handle(ptr::null_mut())
+                .expect("expected error from sf_error, got no error")
+                .during("open")
+                .on(path.display()))
         } else {
             Ok(SndFile {
                 handle :    tmp_sndfile,
-                info :      info
+                info,
+                origin :    Origin::Path(path.to_path_buf()),
+                stream :    ptr::null_mut(),
+                mode :      PhantomData
             })
         }
     }
 
+    /// Run sf_command on the handle with `data` as argument and return the
+    /// raw result of libsndfile.
+    fn command<T>(&self, cmd : ffi::SF_COMMAND, data : &mut T) -> i32 {
+        self.command_sized(cmd, data, mem::size_of::<T>())
+    }
+
+    /// Run sf_command on the handle with the first `size` bytes of `data` as
+    /// argument, for the structures whose used size libsndfile checks.
+    fn command_sized<T>(&self, cmd : ffi::SF_COMMAND, data : &mut T, size : usize) -> i32 {
+        debug_assert!(size <= mem::size_of::<T>());
+        self.clear_stream_error();
+        unsafe {
+            ffi::sf_command(self.handle,
+                            cmd,
+                            data as *mut T as *mut libc::c_void,
+                            size as i32)
+        }
+    }
+
+    /// Run sf_command on the handle for the commands taking no data and a
+    /// value, often a boolean, in place of the size of the data.
+    fn command_value(&self, cmd : ffi::SF_COMMAND, value : i32) -> i32 {
+        self.clear_stream_error();
+        unsafe {
+            ffi::sf_command(self.handle, cmd, ptr::null_mut(), value)
+        }
+    }
+
+    /// Run sf_command on the handle with the whole of `data` as argument, for
+    /// the commands taking variable sized structures.
+    fn command_buffer<T>(&self, cmd : ffi::SF_COMMAND, data : &mut [T]) -> i32 {
+        self.clear_stream_error();
+        unsafe {
+            ffi::sf_command(self.handle,
+                            cmd,
+                            data.as_mut_ptr() as *mut libc::c_void,
+                            mem::size_of_val(data) as i32)
+        }
+    }
+
+    /// The error of a libsndfile call which reported a failure: the error
+    /// recorded by the stream or the handle, or `fallback` when libsndfile
+    /// failed without recording one, as most commands do.
+    fn command_error(&self, fallback : ErrorKind, operation : &'static str) -> SndFileError {
+        self.error()
+            .unwrap_or_else(|| SndFileError::new(fallback).on(&self.origin))
+            .during(operation)
+    }
+
     /// Return the SndInfo struct of the current music.
     pub fn get_sndinfo(&self) -> SndInfo {
-        self.info.clone()
+        self.info
+    }
+
+    /**
+     * Open the file behind this SndFile a second time.
+     *
+     * The returned SndFile owns its own libsndfile handle, so both objects
+     * can be used and closed independently.
+     *
+     * The new handle is opened with the mode of its type, which can differ
+     * from the mode of this SndFile but must be readable, as the format is
+     * read from the file.
+     *
+     * Return Ok() containing the new SndFile on success, NotReopenable if
+     * the SndFile was not opened from a path, an other error otherwise.
+     */
+    pub fn reopen<N : Readable>(&self) -> SndFileResult<SndFile<N>> {
+        match self.origin {
+            Origin::Path(ref path) => SndFile::new(path),
+            _ => Err(SndFileError::new(ErrorKind::NotReopenable)
+                     .during("reopen")
+                     .on(&self.origin))
+        }
     }
 
     /**
@@ -414,39 +471,225 @@ impl SndFile {
      * # Argument
      * * `string_type` - The type of the tag to retrieve
      *
+     * The tag is decoded as UTF-8, invalid sequences being replaced by
+     * U+FFFD; use get_string_bytes to get the bytes stored in the file.
+     *
      * Return Some(String) if the tag is found, None otherwise.
      */
     pub fn get_string(&self, string_type : StringSoundType) -> Option<String> {
+        self.get_string_bytes(string_type)
+            .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+    }
+
+    /**
+     * Retrieve the raw bytes of a tag contained by the music.
+     *
+     * # Argument
+     * * `string_type` - The type of the tag to retrieve
+     *
+     * Return Some(Vec<u8>) if the tag is found, None otherwise.
+     */
+    pub fn get_string_bytes(&self, string_type : StringSoundType) -> Option<Vec<u8>> {
         let c_string = unsafe {
             ffi::sf_get_string(self.handle, string_type as i32)
         };
         if c_string.is_null() {
             None
         } else {
-            Some(unsafe {
-                std::str::from_utf8_unchecked(CStr::from_ptr(
-                    c_string).to_bytes()).to_string()
-            })
+            Some(unsafe { CStr::from_ptr(c_string) }.to_bytes().to_vec())
         }
     }
 
     /**
-     * Set a tag on the music file.
+     * List the types of tag the major format of the file can store.
      *
-     * # Arguments
-     * * `string_type` - The type of the tag to set
-     * * `string` - The string to set.
+     * See MajorFormat::supported_strings.
+     */
+    pub fn supported_strings(&self) -> &'static [StringSoundType] {
+        self.info.format().major().supported_strings()
+    }
+
+    /// Run sf_seek with `pos` and the mode bits `mode` selecting the read or
+    /// write pointer, 0 for both.
+    fn seek_with(&self,
+                 pos : SeekFrom,
+                 mode : i32,
+                 operation : &'static str) -> SndFileResult<u64> {
+        let args = match pos {
+            SeekFrom::Start(frames) if frames <= i64::MAX as u64 =>
+                Some((frames as i64, ffi::SEEK_SET)),
+            SeekFrom::Start(_) => None,
+            SeekFrom::Current(frames) => Some((frames, ffi::SEEK_CUR)),
+            SeekFrom::End(frames) => Some((frames, ffi::SEEK_END))
+        };
+        let kind = match args {
+            _ if self.info.seekable == ffi::SF_FALSE => ErrorKind::NotSeekable,
+            None => ErrorKind::InvalidValue,
+            Some((frames, whence)) => {
+                self.clear_stream_error();
+                let position = unsafe { ffi::sf_seek(self.handle, frames, mode | whence) };
+                if position >= 0 {
+                    return Ok(position as u64);
+                }
+                if let Some(err) = self.error() {
+                    return Err(err.during(operation));
+                }
+                ErrorKind::InvalidValue
+            }
+        };
+        Err(SndFileError::new(kind)
+            .during(operation)
+            .on(&self.origin))
+    }
+
+    /**
+     * Move in the audio data, in frames.
      *
-     * Return () on success, Err otherwise
+     * In read-write mode both the read and the write positions move, see
+     * seek_read and seek_write to move only one of them.
+     *
+     * # Argument
+     * * `pos` - The position to move to, in frames from the start, the
+     *   current position or the end of the audio data
+     *
+     * Return Ok() containing the new position from the start, NotSeekable if
+     * the file is not seekable, as for pipes, an other error otherwise.
      */
-    pub fn set_string(&mut self,
-                      string_type : StringSoundType,
-                      string : &str) -> SndFileResult<()> {
-        let error_code = unsafe {
-            let c_str = string.as_ptr() as *const i8;
-            ffi::sf_set_string(self.handle, string_type as i32, c_str)
+    pub fn seek(&mut self, pos : SeekFrom) -> SndFileResult<u64> {
+        self.seek_with(pos, 0, "seek")
+    }
+
+    /// Get the current position in the audio data, in frames from the start.
+    /// In read-write mode this is the read position.
+    pub fn position(&self) -> SndFileResult<u64> {
+        let mode = match M::open_mode() {
+            OpenMode::Write => ffi::SFM_WRITE,
+            _ => ffi::SFM_READ
+        };
+        self.seek_with(SeekFrom::Current(0), mode, "position")
+    }
+
+    /// Move back to the start of the audio data.
+    pub fn rewind(&mut self) -> SndFileResult<()> {
+        self.seek_with(SeekFrom::Start(0), 0, "rewind").map(|_| ())
+    }
+
+    /**
+     * Close the SndFile object.
+     *
+     * Dropping the SndFile closes it too, but discards the error returned by
+     * libsndfile; use this function when that error matters.
+     *
+     * For virtual I/O SndFiles, the stream is flushed once libsndfile has
+     * rewritten the header, and its errors are reported.
+     *
+     * Return Ok(()) if destruction success, an error otherwise.
+     */
+    pub fn close(mut self) -> SndFileResult<()> {
+        self.clear_stream_error();
+        let handle = mem::replace(&mut self.handle, ptr::null_mut());
+        let error_code = unsafe { ffi::sf_close(handle) };
+        let stream_error = if self.stream.is_null() {
+            None
+        } else {
+            let vs = unsafe { &mut *self.stream };
+            vs.take_error().or_else(|| vs.flush().err())
         };
-        SndFileError::code_to_result(error_code, ())
+        let err = match stream_error {
+            Some(err) => Some(SndFileError::from(err)),
+            None => SndFileError::from_code(error_code)
+        };
+        match err {
+            Some(err) => Err(err.during("close").on(&self.origin)),
+            None => Ok(())
+        }
+    }
+
+    /**
+     * Get the last error if one exists or `None` if there has not been an
+     * error.
+     *
+     * For virtual I/O SndFiles, an error of the stream takes precedence over
+     * the error reported by libsndfile, and is only reported once.
+     */
+    pub fn error(&self) -> Option<SndFileError> {
+        let err = match self.take_stream_error() {
+            Some(err) => Some(SndFileError::from(err)),
+            None => SndFileError::from_handle(self.handle)
+        };
+        err.map(|err| err.on(&self.origin))
+    }
+
+    /// Take the error recorded by the stream of a virtual I/O SndFile.
+    fn take_stream_error(&self) -> Option<io::Error> {
+        if self.stream.is_null() {
+            None
+        } else {
+            unsafe { (*self.stream).take_error() }
+        }
+    }
+
+    /// Forget any error recorded by the stream before a libsndfile call, so
+    /// that error() only reports the errors raised during that call.
+    fn clear_stream_error(&self) {
+        self.take_stream_error();
+    }
+
+    fn channels(&self) -> usize {
+        self.info.channels.max(1) as usize
+    }
+
+    /// The number of samples to preallocate to read the whole file, from the
+    /// number of frames in the header. The header may be wrong or libsndfile
+    /// may not know the length, as for pipes, so the hint is bounded and 0
+    /// for unknown lengths.
+    fn samples_hint(&self) -> usize {
+        if self.info.frames < 0 || self.info.frames == i64::MAX {
+            return 0;
+        }
+        (self.info.frames as usize)
+            .checked_mul(self.channels())
+            .map_or(MAX_SAMPLES_HINT, |samples| samples.min(MAX_SAMPLES_HINT))
+    }
+
+    /// Get the number of frames held by a buffer of `len` items, checking
+    /// that it only contains whole frames.
+    fn frames_in(&self, len : usize, operation : &'static str) -> SndFileResult<usize> {
+        if len.is_multiple_of(self.channels()) {
+            Ok(len / self.channels())
+        } else {
+            Err(SndFileError::new(ErrorKind::InvalidBufferLength)
+                .during(operation)
+                .on(&self.origin))
+        }
+    }
+
+    /// Turn the count returned by libsndfile into a result, reporting the
+    /// error of the handle when fewer than `requested` were processed.
+    fn check_count(&self,
+                   requested : usize,
+                   count : i64,
+                   operation : &'static str) -> SndFileResult<usize> {
+        if count < 0 || (count as usize) < requested {
+            if let Some(err) = self.error() {
+                return Err(err.during(operation));
+            }
+        }
+        Ok(count.max(0) as usize)
+    }
+}
+
+impl SndFile<mode::Write> {
+    /**
+     * Start building a new sound file to write at the given path.
+     *
+     * # Argument
+     * * `path` - The path of the file to create
+     *
+     * Return a WriterBuilder to set the format of the file and open it.
+     */
+    pub fn create<P : AsRef<Path>>(path : P) -> WriterBuilder {
+        WriterBuilder::new(path)
     }
 
     /**
@@ -457,334 +700,587 @@ impl SndFile {
      *
      * Return true if the struct is valid, false otherwise.
      */
-    pub fn check_format<'r>(info : &'r SndInfo) -> bool {
+    pub fn check_format(info : &SndInfo) -> bool {
         match unsafe {ffi::sf_format_check(info) } {
             ffi::SF_TRUE    => true,
             ffi::SF_FALSE   => false,
             _               => unreachable!()
         }
     }
+}
 
+impl SndFile<mode::ReadWrite> {
+    /**
+     * Move the read position in the audio data, leaving the write position
+     * as is.
+     *
+     * # Argument
+     * * `pos` - The position to move to, in frames
+     *
+     * Return Ok() containing the new read position from the start, the error
+     * otherwise.
+     */
+    pub fn seek_read(&mut self, pos : SeekFrom) -> SndFileResult<u64> {
+        self.seek_with(pos, ffi::SFM_READ, "seek_read")
+    }
 
     /**
-     * Close the SndFile object.
+     * Move the write position in the audio data, leaving the read position
+     * as is.
      *
-     * This function must be called before the exist of the program to destroy
-     * all the resources.
+     * # Argument
+     * * `pos` - The position to move to, in frames
      *
-     * Return NoError if destruction success, an other error code otherwise.
+     * Return Ok() containing the new write position from the start, the
+     * error otherwise.
      */
-    pub fn close(&self) -> SndFileResult<()> {
-        let error_code = unsafe { ffi::sf_close(self.handle) };
-        SndFileError::code_to_result(error_code, ())
+    pub fn seek_write(&mut self, pos : SeekFrom) -> SndFileResult<u64> {
+        self.seek_with(pos, ffi::SFM_WRITE, "seek_write")
     }
+}
 
+impl<M : Readable> SndFile<M> {
     /**
-     * If the file is opened Write or ReadWrite, call the operating system's
-     * function to force the writing of all file cache buffers to disk.
-     * If the file is opened Read no action is taken.
-     * If the file is opened Read no action is taken.
+     * Read items of any sample type
+     *
+     * # Argument
+     * * `array` - The array to fill with the items, its length must be a
+     *   multiple of the number of channels.
+     *
+     * Return Ok() containing the count of items read, the error otherwise.
      */
-    pub fn write_sync(&mut self) {
-        unsafe {
-            ffi::sf_write_sync(self.handle)
-        }
+    pub fn read<T : Sample>(&mut self, array : &mut [T]) -> SndFileResult<usize> {
+        let items = self.frames_in(array.len(), "read")? * self.channels();
+        self.clear_stream_error();
+        let read = unsafe { T::sf_read(self.handle, array.as_mut_ptr(), items as i64) };
+        self.check_count(items, read, "read")
     }
 
     /**
-     * Move in the audio file
+     * Read frames of any sample type
      *
-     * The non audio data are ignored
+     * # Argument
+     * * `array` - The array to fill with the frames, its length must be a
+     *   multiple of the number of channels.
      *
-     * # Arguments
-     * * `frames` - The position to move in frames
-     * * `whence` - The seek mode from the enum SeekMode
+     * Return Ok() containing the count of frames read, the error otherwise.
      */
-    pub fn seek(&mut self, frames : i64, whence : SeekMode) -> i64 {
-        unsafe {
-            ffi::sf_seek(self.handle, frames, whence as i32)
-        }
+    pub fn read_frames<T : Sample>(&mut self, array : &mut [T]) -> SndFileResult<usize> {
+        let frames = self.frames_in(array.len(), "read_frames")?;
+        self.clear_stream_error();
+        let read = unsafe { T::sf_readf(self.handle, array.as_mut_ptr(), frames as i64) };
+        self.check_count(frames, read, "read_frames")
     }
 
     /**
      * Read items of type i16
      *
-     * # Arguments
-     * * `array` - The array to fill with the items.
-     * * `items` - The max capacity of the array.
+     * # Argument
+     * * `array` - The array to fill with the items, its length must be a
+     *   multiple of the number of channels.
      *
-     * Return the count of items.
+     * Return Ok() containing the count of items read, the error otherwise.
      */
-    pub fn read_i16<'r>(&'r mut self,
-                        array : &'r mut [i16],
-                        items : i64) -> i64 {
-        unsafe {
-            ffi::sf_read_short(self.handle, array.as_mut_ptr(), items)
-        }
+    pub fn read_i16(&mut self, array : &mut [i16]) -> SndFileResult<usize> {
+        self.read(array)
     }
 
     /**
      * Read items of type i32
      *
-     * # Arguments
-     * * `array` - The array to fill with the items.
-     * * `items` - The max capacity of the array.
+     * # Argument
+     * * `array` - The array to fill with the items, its length must be a
+     *   multiple of the number of channels.
      *
-     * Return the count of items.
+     * Return Ok() containing the count of items read, the error otherwise.
      */
-    pub fn read_i32<'r>(&'r mut self,
-                        array : &'r mut [i32],
-                        items : i64) -> i64 {
-        unsafe {
-            ffi::sf_read_int(self.handle, array.as_mut_ptr(), items)
-        }
+    pub fn read_i32(&mut self, array : &mut [i32]) -> SndFileResult<usize> {
+        self.read(array)
     }
 
     /**
      * Read items of type f32
      *
-     * # Arguments
-     * * `array` - The array to fill with the items.
-     * * `items` - The max capacity of the array.
+     * # Argument
+     * * `array` - The array to fill with the items, its length must be a
+     *   multiple of the number of channels.
      *
-     * Return the count of items.
+     * Return Ok() containing the count of items read, the error otherwise.
      */
-    pub fn read_f32<'r>(&'r mut self,
-                        array : &'r mut [f32],
-                        items : i64) -> i64 {
-        unsafe {
-            ffi::sf_read_float(self.handle, array.as_mut_ptr(), items)
-        }
+    pub fn read_f32(&mut self, array : &mut [f32]) -> SndFileResult<usize> {
+        self.read(array)
     }
 
     /**
      * Read items of type f64
      *
-     * # Arguments
-     * * `array` - The array to fill with the items.
-     * * `items` - The max capacity of the array.
+     * # Argument
+     * * `array` - The array to fill with the items, its length must be a
+     *   multiple of the number of channels.
      *
-     * Return the count of items.
+     * Return Ok() containing the count of items read, the error otherwise.
      */
-    pub fn read_f64<'r>(&'r mut self,
-                        array : &'r mut [f64],
-                        items : i64) -> i64 {
-        unsafe {
-            ffi::sf_read_double(self.handle, array.as_mut_ptr(), items)
-        }
+    pub fn read_f64(&mut self, array : &mut [f64]) -> SndFileResult<usize> {
+        self.read(array)
     }
 
     /**
      * Read frames of type i16
      *
-     * # Arguments
-     * * `array` - The array to fill with the frames.
-     * * `items` - The max capacity of the array.
+     * # Argument
+     * * `array` - The array to fill with the frames, its length must be a
+     *   multiple of the number of channels.
      *
-     * Return the count of frames.
+     * Return Ok() containing the count of frames read, the error otherwise.
      */
-    pub fn readf_i16<'r>(&'r mut self,
-                         array : &'r mut [i16],
-                         frames : i64) -> i64 {
-        unsafe {
-            ffi::sf_readf_short(self.handle, array.as_mut_ptr(), frames)
-        }
+    pub fn readf_i16(&mut self, array : &mut [i16]) -> SndFileResult<usize> {
+        self.read_frames(array)
     }
 
     /**
      * Read frames of type i32
      *
-     * # Arguments
-     * * `array` - The array to fill with the frames.
-     * * `items` - The max capacity of the array.
+     * # Argument
+     * * `array` - The array to fill with the frames, its length must be a
+     *   multiple of the number of channels.
      *
-     * Return the count of frames.
+     * Return Ok() containing the count of frames read, the error otherwise.
      */
-    pub fn readf_i32<'r>(&'r mut self,
-                         array : &'r mut [i32],
-                         frames : i64) -> i64 {
-        unsafe {
-            ffi::sf_readf_int(self.handle, array.as_mut_ptr(), frames)
-        }
+    pub fn readf_i32(&mut self, array : &mut [i32]) -> SndFileResult<usize> {
+        self.read_frames(array)
     }
 
     /**
      * Read frames of type f32
      *
-     * # Arguments
-     * * `array` - The array to fill with the frames.
-     * * `items` - The max capacity of the array.
+     * # Argument
+     * * `array` - The array to fill with the frames, its length must be a
+     *   multiple of the number of channels.
      *
-     * Return the count of frames.
+     * Return Ok() containing the count of frames read, the error otherwise.
      */
-    pub fn readf_f32<'r>(&'r mut self,
-                         array : &'r mut [f32],
-                         frames : i64) -> i64 {
-        unsafe {
-            ffi::sf_readf_float(self.handle, array.as_mut_ptr(), frames)
-        }
+    pub fn readf_f32(&mut self, array : &mut [f32]) -> SndFileResult<usize> {
+        self.read_frames(array)
     }
 
     /**
      * Read frames of type f64
      *
-     * # Arguments
-     * * `array` - The array to fill with the frames.
-     * * `items` - The max capacity of the array.
+     * # Argument
+     * * `array` - The array to fill with the frames, its length must be a
+     *   multiple of the number of channels.
+     *
+     * Return Ok() containing the count of frames read, the error otherwise.
+     */
+    pub fn readf_f64(&mut self, array : &mut [f64]) -> SndFileResult<usize> {
+        self.read_frames(array)
+    }
+
+    /**
+     * Read items of type i16, without checking the count against the array.
+     *
+     * # Safety
+     * `items` must not exceed the length of `array`.
+     *
+     * Return the count of items.
+     */
+    pub unsafe fn read_i16_unchecked(&mut self,
+                                     array : &mut [i16],
+                                     items : i64) -> i64 {
+        ffi::sf_read_short(self.handle, array.as_mut_ptr(), items)
+    }
+
+    /**
+     * Read items of type i32, without checking the count against the array.
+     *
+     * # Safety
+     * `items` must not exceed the length of `array`.
+     *
+     * Return the count of items.
+     */
+    pub unsafe fn read_i32_unchecked(&mut self,
+                                     array : &mut [i32],
+                                     items : i64) -> i64 {
+        ffi::sf_read_int(self.handle, array.as_mut_ptr(), items)
+    }
+
+    /**
+     * Read items of type f32, without checking the count against the array.
+     *
+     * # Safety
+     * `items` must not exceed the length of `array`.
+     *
+     * Return the count of items.
+     */
+    pub unsafe fn read_f32_unchecked(&mut self,
+                                     array : &mut [f32],
+                                     items : i64) -> i64 {
+        ffi::sf_read_float(self.handle, array.as_mut_ptr(), items)
+    }
+
+    /**
+     * Read items of type f64, without checking the count against the array.
+     *
+     * # Safety
+     * `items` must not exceed the length of `array`.
+     *
+     * Return the count of items.
+     */
+    pub unsafe fn read_f64_unchecked(&mut self,
+                                     array : &mut [f64],
+                                     items : i64) -> i64 {
+        ffi::sf_read_double(self.handle, array.as_mut_ptr(), items)
+    }
+
+    /**
+     * Read frames of type i16, without checking the count against the array.
+     *
+     * # Safety
+     * `frames` multiplied by the number of channels must not exceed the
+     * length of `array`.
      *
      * Return the count of frames.
      */
-    pub fn readf_f64<'r>(&'r mut self,
-                         array : &'r mut [f64],
-                         frames : i64) -> i64 {
-        unsafe {
-            ffi::sf_readf_double(self.handle, array.as_mut_ptr(), frames)
-        }
+    pub unsafe fn readf_i16_unchecked(&mut self,
+                                      array : &mut [i16],
+                                      frames : i64) -> i64 {
+        ffi::sf_readf_short(self.handle, array.as_mut_ptr(), frames)
     }
 
     /**
-     * Write items of type i16
+     * Read frames of type i32, without checking the count against the array.
+     *
+     * # Safety
+     * `frames` multiplied by the number of channels must not exceed the
+     * length of `array`.
+     *
+     * Return the count of frames.
+     */
+    pub unsafe fn readf_i32_unchecked(&mut self,
+                                      array : &mut [i32],
+                                      frames : i64) -> i64 {
+        ffi::sf_readf_int(self.handle, array.as_mut_ptr(), frames)
+    }
+
+    /**
+     * Read frames of type f32, without checking the count against the array.
+     *
+     * # Safety
+     * `frames` multiplied by the number of channels must not exceed the
+     * length of `array`.
+     *
+     * Return the count of frames.
+     */
+    pub unsafe fn readf_f32_unchecked(&mut self,
+                                      array : &mut [f32],
+                                      frames : i64) -> i64 {
+        ffi::sf_readf_float(self.handle, array.as_mut_ptr(), frames)
+    }
+
+    /**
+     * Read frames of type f64, without checking the count against the array.
+     *
+     * # Safety
+     * `frames` multiplied by the number of channels must not exceed the
+     * length of `array`.
+     *
+     * Return the count of frames.
+     */
+    pub unsafe fn readf_f64_unchecked(&mut self,
+                                      array : &mut [f64],
+                                      frames : i64) -> i64 {
+        ffi::sf_readf_double(self.handle, array.as_mut_ptr(), frames)
+    }
+}
+
+impl<M : Writable> SndFile<M> {
+    /**
+     * Set a tag on the music file.
      *
      * # Arguments
-     * * `array` - The array of items to write.
-     * * `items` - The number of items to write.
+     * * `string_type` - The type of the tag to set
+     * * `string` - The string to set, which must not contain NUL bytes.
      *
-     * Return the count of wrote items.
+     * Return () on success, Err otherwise
      */
-    pub fn write_i16<'r>(&'r mut self,
-                         array : &'r mut [i16],
-                         items : i64) -> i64 {
+    pub fn set_string(&mut self,
+                      string_type : StringSoundType,
+                      string : &str) -> SndFileResult<()> {
+        let c_string = match CString::new(string) {
+            Ok(c_string) => c_string,
+            Err(_) => return Err(SndFileError::new(ErrorKind::InvalidString)
+                                 .during("set_string")
+                                 .on(&self.origin))
+        };
+        self.clear_stream_error();
+        let error_code = unsafe {
+            ffi::sf_set_string(self.handle, string_type as i32, c_string.as_ptr())
+        };
+        match ErrorKind::from_code(error_code) {
+            Some(kind) => Err(self.command_error(kind, "set_string")),
+            None => Ok(())
+        }
+    }
+
+    /**
+     * Call the operating system's function to force the writing of all file
+     * cache buffers to disk.
+     */
+    pub fn write_sync(&mut self) {
         unsafe {
-            ffi::sf_write_short(self.handle, array.as_mut_ptr(), items)
+            ffi::sf_write_sync(self.handle)
         }
     }
 
+    /**
+     * Write items of any sample type
+     *
+     * # Argument
+     * * `array` - The items to write, its length must be a multiple of the
+     *   number of channels.
+     *
+     * Return Ok() containing the count of written items, the error otherwise.
+     */
+    pub fn write<T : Sample>(&mut self, array : &[T]) -> SndFileResult<usize> {
+        let items = self.frames_in(array.len(), "write")? * self.channels();
+        self.clear_stream_error();
+        let written = unsafe { T::sf_write(self.handle, array.as_ptr(), items as i64) };
+        self.check_count(items, written, "write")
+    }
+
+    /**
+     * Write frames of any sample type
+     *
+     * # Argument
+     * * `array` - The frames to write, its length must be a multiple of the
+     *   number of channels.
+     *
+     * Return Ok() containing the count of written frames, the error otherwise.
+     */
+    pub fn write_frames<T : Sample>(&mut self, array : &[T]) -> SndFileResult<usize> {
+        let frames = self.frames_in(array.len(), "write_frames")?;
+        self.clear_stream_error();
+        let written = unsafe { T::sf_writef(self.handle, array.as_ptr(), frames as i64) };
+        self.check_count(frames, written, "write_frames")
+    }
+
+    /**
+     * Write items of type i16
+     *
+     * # Argument
+     * * `array` - The items to write, its length must be a multiple of the
+     *   number of channels.
+     *
+     * Return Ok() containing the count of written items, the error otherwise.
+     */
+    pub fn write_i16(&mut self, array : &[i16]) -> SndFileResult<usize> {
+        self.write(array)
+    }
+
     /**
      * Write items of type i32
      *
-     * # Arguments
-     * * `array` - The array of items to write.
-     * * `items` - The number of items to write.
+     * # Argument
+     * * `array` - The items to write, its length must be a multiple of the
+     *   number of channels.
      *
-     * Return the count of wrote items.
+     * Return Ok() containing the count of written items, the error otherwise.
      */
-    pub fn write_i32<'r>(&'r mut self,
-                         array : &'r mut [i32],
-                         items : i64) -> i64 {
-        unsafe {
-            ffi::sf_write_int(self.handle, array.as_mut_ptr(), items)
-        }
+    pub fn write_i32(&mut self, array : &[i32]) -> SndFileResult<usize> {
+        self.write(array)
     }
 
     /**
      * Write items of type f32
      *
-     * # Arguments
-     * * `array` - The array of items to write.
-     * * `items` - The number of items to write.
+     * # Argument
+     * * `array` - The items to write, its length must be a multiple of the
+     *   number of channels.
      *
-     * Return the count of wrote items.
+     * Return Ok() containing the count of written items, the error otherwise.
      */
-    pub fn write_f32<'r>(&'r mut self,
-                         array : &'r mut [f32],
-                         items : i64) -> i64 {
-        unsafe {
-            ffi::sf_write_float(self.handle, array.as_mut_ptr(), items)
-        }
+    pub fn write_f32(&mut self, array : &[f32]) -> SndFileResult<usize> {
+        self.write(array)
     }
 
     /**
      * Write items of type f64
      *
-     * # Arguments
-     * * `array` - The array of items to write.
-     * * `items` - The number of items to write.
+     * # Argument
+     * * `array` - The items to write, its length must be a multiple of the
+     *   number of channels.
      *
-     * Return the count of wrote items.
+     * Return Ok() containing the count of written items, the error otherwise.
      */
-    pub fn write_f64<'r>(&'r mut self,
-                         array : &'r mut [f64],
-                         items : i64) -> i64 {
-        unsafe {
-            ffi::sf_write_double(self.handle, array.as_mut_ptr(), items)
-        }
+    pub fn write_f64(&mut self, array : &[f64]) -> SndFileResult<usize> {
+        self.write(array)
     }
 
     /**
      * Write frames of type i16
      *
-     * # Arguments
-     * * `array` - The array of frames to write.
-     * * `items` - The number of frames to write.
+     * # Argument
+     * * `array` - The frames to write, its length must be a multiple of the
+     *   number of channels.
      *
-     * Return the count of wrote frames.
+     * Return Ok() containing the count of written frames, the error otherwise.
      */
-    pub fn writef_i16<'r>(&'r mut self,
-                          array : &'r mut [i16],
-                          frames : i64) -> i64 {
-        unsafe {
-            ffi::sf_writef_short(self.handle, array.as_mut_ptr(), frames)
-        }
+    pub fn writef_i16(&mut self, array : &[i16]) -> SndFileResult<usize> {
+        self.write_frames(array)
     }
 
     /**
      * Write frames of type i32
      *
-     * # Arguments
-     * * `array` - The array of frames to write.
-     * * `items` - The number of frames to write.
+     * # Argument
+     * * `array` - The frames to write, its length must be a multiple of the
+     *   number of channels.
      *
-     * Return the count of wrote frames.
+     * Return Ok() containing the count of written frames, the error otherwise.
      */
-    pub fn writef_i32<'r>(&'r mut self,
-                          array : &'r mut [i32],
-                          frames : i64) -> i64 {
-        unsafe {
-            ffi::sf_writef_int(self.handle, array.as_mut_ptr(), frames)
-        }
+    pub fn writef_i32(&mut self, array : &[i32]) -> SndFileResult<usize> {
+        self.write_frames(array)
     }
 
     /**
      * Write frames of type f32
      *
-     * # Arguments
-     * * `array` - The array of frames to write.
-     * * `items` - The number of frames to write.
+     * # Argument
+     * * `array` - The frames to write, its length must be a multiple of the
+     *   number of channels.
      *
-     * Return the count of wrote frames.
+     * Return Ok() containing the count of written frames, the error otherwise.
      */
-    pub fn writef_f32<'r>(&'r mut self,
-                          array : &'r mut [f32],
-                          frames : i64) -> i64 {
-        unsafe {
-            ffi::sf_writef_float(self.handle, array.as_mut_ptr(), frames)
-        }
+    pub fn writef_f32(&mut self, array : &[f32]) -> SndFileResult<usize> {
+        self.write_frames(array)
     }
 
     /**
      * Write frames of type f64
      *
-     * # Arguments
-     * * `array` - The array of frames to write.
-     * * `items` - The number of frames to write.
+     * # Argument
+     * * `array` - The frames to write, its length must be a multiple of the
+     *   number of channels.
      *
-     * Return the count of wrote frames.
+     * Return Ok() containing the count of written frames, the error otherwise.
      */
-    pub fn writef_f64<'r>(&'r mut self,
-                          array : &'r mut [f64],
-                          frames : i64) -> i64 {
-        unsafe {
-            ffi::sf_writef_double(self.handle, array.as_mut_ptr(), frames)
-        }
+    pub fn writef_f64(&mut self, array : &[f64]) -> SndFileResult<usize> {
+        self.write_frames(array)
     }
 
     /**
-     * Get the last error if one exists or `None` if there has not been an
-     * error.
+     * Write items of type i16, without checking the count against the array.
+     *
+     * # Safety
+     * `items` must not exceed the length of `array`.
+     *
+     * Return the count of written items.
      */
-    pub fn error(&self) -> Option<SndFileError> {
-        SndFileError::from_code(unsafe {
-            ffi::sf_error(self.handle)
-        })
+    pub unsafe fn write_i16_unchecked(&mut self,
+                                      array : &[i16],
+                                      items : i64) -> i64 {
+        ffi::sf_write_short(self.handle, array.as_ptr(), items)
+    }
+
+    /**
+     * Write items of type i32, without checking the count against the array.
+     *
+     * # Safety
+     * `items` must not exceed the length of `array`.
+     *
+     * Return the count of written items.
+     */
+    pub unsafe fn write_i32_unchecked(&mut self,
+                                      array : &[i32],
+                                      items : i64) -> i64 {
+        ffi::sf_write_int(self.handle, array.as_ptr(), items)
+    }
+
+    /**
+     * Write items of type f32, without checking the count against the array.
+     *
+     * # Safety
+     * `items` must not exceed the length of `array`.
+     *
+     * Return the count of written items.
+     */
+    pub unsafe fn write_f32_unchecked(&mut self,
+                                      array : &[f32],
+                                      items : i64) -> i64 {
+        ffi::sf_write_float(self.handle, array.as_ptr(), items)
+    }
+
+    /**
+     * Write items of type f64, without checking the count against the array.
+     *
+     * # Safety
+     * `items` must not exceed the length of `array`.
+     *
+     * Return the count of written items.
+     */
+    pub unsafe fn write_f64_unchecked(&mut self,
+                                      array : &[f64],
+                                      items : i64) -> i64 {
+        ffi::sf_write_double(self.handle, array.as_ptr(), items)
+    }
+
+    /**
+     * Write frames of type i16, without checking the count against the array.
+     *
+     * # Safety
+     * `frames` multiplied by the number of channels must not exceed the
+     * length of `array`.
+     *
+     * Return the count of written frames.
+     */
+    pub unsafe fn writef_i16_unchecked(&mut self,
+                                       array : &[i16],
+                                       frames : i64) -> i64 {
+        ffi::sf_writef_short(self.handle, array.as_ptr(), frames)
+    }
+
+    /**
+     * Write frames of type i32, without checking the count against the array.
+     *
+     * # Safety
+     * `frames` multiplied by the number of channels must not exceed the
+     * length of `array`.
+     *
+     * Return the count of written frames.
+     */
+    pub unsafe fn writef_i32_unchecked(&mut self,
+                                       array : &[i32],
+                                       frames : i64) -> i64 {
+        ffi::sf_writef_int(self.handle, array.as_ptr(), frames)
+    }
+
+    /**
+     * Write frames of type f32, without checking the count against the array.
+     *
+     * # Safety
+     * `frames` multiplied by the number of channels must not exceed the
+     * length of `array`.
+     *
+     * Return the count of written frames.
+     */
+    pub unsafe fn writef_f32_unchecked(&mut self,
+                                       array : &[f32],
+                                       frames : i64) -> i64 {
+        ffi::sf_writef_float(self.handle, array.as_ptr(), frames)
+    }
+
+    /**
+     * Write frames of type f64, without checking the count against the array.
+     *
+     * # Safety
+     * `frames` multiplied by the number of channels must not exceed the
+     * length of `array`.
+     *
+     * Return the count of written frames.
+     */
+    pub unsafe fn writef_f64_unchecked(&mut self,
+                                       array : &[f64],
+                                       frames : i64) -> i64 {
+        ffi::sf_writef_double(self.handle, array.as_ptr(), frames)
     }
 }
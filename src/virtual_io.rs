@@ -0,0 +1,400 @@
+// The MIT License (MIT)
+//
+// Copyright (c) 2013 Jeremy Letang (letang.jeremy@gmail.com)
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy of
+// this software and associated documentation files (the "Software"), to deal in
+// the Software without restriction, including without limitation the rights to
+// use, copy, modify, merge, publish, distribute, sublicense, and/or sell copies of
+// the Software, and to permit persons to whom the Software is furnished to do so,
+// subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS
+// FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR
+// COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER LIABILITY, WHETHER
+// IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT OF OR IN
+// CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+
+//! Virtual I/O: SndFile objects backed by Rust streams instead of files.
+
+use std::any::Any;
+use std::fmt;
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::marker::PhantomData;
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+use std::slice;
+use libc::c_void;
+
+use ffi;
+use mode::{self, Mode};
+use {Origin, SndFile, SndFileError, SndFileResult, SndInfo};
+
+/// The operations libsndfile needs from a stream, whatever the mode.
+trait Stream {
+    fn read(&mut self, buf : &mut [u8]) -> io::Result<usize>;
+    fn write(&mut self, buf : &[u8]) -> io::Result<usize>;
+    fn seek(&mut self, pos : SeekFrom) -> io::Result<u64>;
+    fn flush(&mut self) -> io::Result<()>;
+}
+
+fn read_only() -> io::Error {
+    io::Error::other("the stream was opened read-only")
+}
+
+fn write_only() -> io::Error {
+    io::Error::other("the stream was opened write-only")
+}
+
+struct Reader<R>(R);
+
+impl<R : Read + Seek> Stream for Reader<R> {
+    fn read(&mut self, buf : &mut [u8]) -> io::Result<usize> { self.0.read(buf) }
+    fn write(&mut self, _ : &[u8]) -> io::Result<usize> { Err(read_only()) }
+    fn seek(&mut self, pos : SeekFrom) -> io::Result<u64> { self.0.seek(pos) }
+    fn flush(&mut self) -> io::Result<()> { Ok(()) }
+}
+
+struct Writer<W>(W);
+
+impl<W : Write + Seek> Stream for Writer<W> {
+    fn read(&mut self, _ : &mut [u8]) -> io::Result<usize> { Err(write_only()) }
+    fn write(&mut self, buf : &[u8]) -> io::Result<usize> { self.0.write(buf) }
+    fn seek(&mut self, pos : SeekFrom) -> io::Result<u64> { self.0.seek(pos) }
+    fn flush(&mut self) -> io::Result<()> { self.0.flush() }
+}
+
+struct ReadWriter<RW>(RW);
+
+impl<RW : Read + Write + Seek> Stream for ReadWriter<RW> {
+    fn read(&mut self, buf : &mut [u8]) -> io::Result<usize> { self.0.read(buf) }
+    fn write(&mut self, buf : &[u8]) -> io::Result<usize> { self.0.write(buf) }
+    fn seek(&mut self, pos : SeekFrom) -> io::Result<u64> { self.0.seek(pos) }
+    fn flush(&mut self) -> io::Result<()> { self.0.flush() }
+}
+
+/// The user data handed to libsndfile: the stream and the last I/O error
+/// raised by one of the callbacks.
+pub struct VirtualStream {
+    stream : Box<dyn Stream>,
+    error : Option<io::Error>
+}
+
+impl fmt::Debug for VirtualStream {
+    fn fmt(&self, f : &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("VirtualStream").field("error", &self.error).finish()
+    }
+}
+
+impl VirtualStream {
+    /// Take the last error raised by the stream, if any.
+    pub fn take_error(&mut self) -> Option<io::Error> {
+        self.error.take()
+    }
+
+    /// Flush the data buffered by the stream.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.stream.flush()
+    }
+
+    /// Run `f` on the stream, recording any error or panic and returning
+    /// `on_error` to libsndfile in that case.
+    fn call<F>(&mut self, on_error : i64, f : F) -> i64
+        where F : FnOnce(&mut dyn Stream) -> io::Result<i64> {
+        let stream = &mut *self.stream;
+        match panic::catch_unwind(AssertUnwindSafe(|| f(stream))) {
+            Ok(Ok(value)) => value,
+            Ok(Err(err)) => {
+                self.error = Some(err);
+                on_error
+            },
+            Err(payload) => {
+                self.error = Some(panic_to_error(payload));
+                on_error
+            }
+        }
+    }
+}
+
+fn panic_to_error(payload : Box<dyn Any + Send>) -> io::Error {
+    let msg = match payload.downcast_ref::<&str>() {
+        Some(msg) => msg.to_string(),
+        None => match payload.downcast_ref::<String>() {
+            Some(msg) => msg.clone(),
+            None => "unknown panic".to_string()
+        }
+    };
+    io::Error::other(format!("panic in virtual I/O callback: {}", msg))
+}
+
+unsafe fn user_stream<'a>(user_data : *mut c_void) -> &'a mut VirtualStream {
+    &mut *(user_data as *mut VirtualStream)
+}
+
+extern "C" fn vio_get_filelen(user_data : *mut c_void) -> i64 {
+    let vs = unsafe { user_stream(user_data) };
+    vs.call(-1, |stream| {
+        let current = stream.seek(SeekFrom::Current(0))?;
+        let len = stream.seek(SeekFrom::End(0))?;
+        stream.seek(SeekFrom::Start(current))?;
+        Ok(len as i64)
+    })
+}
+
+extern "C" fn vio_seek(offset : i64, whence : i32, user_data : *mut c_void) -> i64 {
+    let vs = unsafe { user_stream(user_data) };
+    vs.call(-1, |stream| {
+        let pos = match whence {
+            ffi::SEEK_SET => {
+                if offset < 0 {
+                    return Err(io::Error::new(io::ErrorKind::InvalidInput,
+                                              "seek to a negative position"));
+                }
+                SeekFrom::Start(offset as u64)
+            },
+            ffi::SEEK_CUR => SeekFrom::Current(offset),
+            ffi::SEEK_END => SeekFrom::End(offset),
+            _ => return Err(io::Error::new(io::ErrorKind::InvalidInput,
+                                           "invalid seek mode"))
+        };
+        stream.seek(pos).map(|pos| pos as i64)
+    })
+}
+
+extern "C" fn vio_read(ptr : *mut c_void, count : i64, user_data : *mut c_void) -> i64 {
+    if ptr.is_null() || count <= 0 {
+        return 0;
+    }
+    let vs = unsafe { user_stream(user_data) };
+    let buf = unsafe { slice::from_raw_parts_mut(ptr as *mut u8, count as usize) };
+    vs.call(0, |stream| {
+        let mut filled = 0;
+        while filled < buf.len() {
+            match stream.read(&mut buf[filled..]) {
+                Ok(0) => break,
+                Ok(n) => filled += n,
+                Err(ref err) if err.kind() == io::ErrorKind::Interrupted => {},
+                Err(err) => return Err(err)
+            }
+        }
+        Ok(filled as i64)
+    })
+}
+
+extern "C" fn vio_write(ptr : *const c_void, count : i64, user_data : *mut c_void) -> i64 {
+    if ptr.is_null() || count <= 0 {
+        return 0;
+    }
+    let vs = unsafe { user_stream(user_data) };
+    let buf = unsafe { slice::from_raw_parts(ptr as *const u8, count as usize) };
+    vs.call(0, |stream| {
+        let mut written = 0;
+        while written < buf.len() {
+            match stream.write(&buf[written..]) {
+                Ok(0) => return Err(io::Error::new(io::ErrorKind::WriteZero,
+                                                   "failed to write whole buffer")),
+                Ok(n) => written += n,
+                Err(ref err) if err.kind() == io::ErrorKind::Interrupted => {},
+                Err(err) => return Err(err)
+            }
+        }
+        Ok(written as i64)
+    })
+}
+
+extern "C" fn vio_tell(user_data : *mut c_void) -> i64 {
+    let vs = unsafe { user_stream(user_data) };
+    vs.call(-1, |stream| stream.seek(SeekFrom::Current(0)).map(|pos| pos as i64))
+}
+
+fn open_virtual<M : Mode>(stream : Box<dyn Stream>,
+                          mut info : SndInfo) -> SndFileResult<SndFile<M>> {
+    let vs = Box::into_raw(Box::new(VirtualStream {
+        stream,
+        error : None
+    }));
+    let mut callbacks = ffi::VirtualIo {
+        get_filelen : vio_get_filelen,
+        seek : vio_seek,
+        read : vio_read,
+        write : vio_write,
+        tell : vio_tell
+    };
+    let handle = unsafe {
+        ffi::sf_open_virtual(&mut callbacks,
+                             M::open_mode() as i32,
+                             &mut info,
+                             vs as *mut c_void)
+    };
+    if handle.is_null() {
+        let mut vs = unsafe { Box::from_raw(vs) };
+        let err = match vs.error.take() {
+            Some(err) => SndFileError::from(err),
+            None => SndFileError::from_handle(ptr::null_mut())
+                .expect("expected error from sf_error, got no error")
+        };
+        Err(err.during("open").on(Origin::Stream))
+    } else {
+        // Errors libsndfile recovered from while opening are not reported.
+        unsafe { (*vs).error = None; }
+        Ok(SndFile {
+            handle,
+            info,
+            origin :    Origin::Stream,
+            stream :    vs,
+            mode :      PhantomData
+        })
+    }
+}
+
+impl SndFile<mode::Read> {
+    /**
+     * Construct a read only SndFile object over a Rust stream.
+     *
+     * The SndFile takes ownership of the stream and drops it when closed.
+     *
+     * # Argument
+     * * `reader` - The stream containing the music
+     *
+     * Return Ok() containing the SndFile on success, the error otherwise.
+     */
+    pub fn from_reader<R>(reader : R) -> SndFileResult<SndFile<mode::Read>>
+        where R : Read + Seek + 'static {
+        open_virtual(Box::new(Reader(reader)), SndInfo::default())
+    }
+}
+
+impl SndFile<mode::Write> {
+    /**
+     * Construct a write only SndFile object over a Rust stream.
+     *
+     * The SndFile takes ownership of the stream and drops it when closed.
+     *
+     * # Arguments
+     * * `writer` - The stream to write the music to
+     * * `info` - The format, sample rate and channels of the music
+     *
+     * Return Ok() containing the SndFile on success, the error otherwise.
+     */
+    pub fn from_writer<W>(writer : W, info : SndInfo) -> SndFileResult<SndFile<mode::Write>>
+        where W : Write + Seek + 'static {
+        open_virtual(Box::new(Writer(writer)), info)
+    }
+}
+
+impl SndFile<mode::ReadWrite> {
+    /**
+     * Construct a read and write SndFile object over a Rust stream.
+     *
+     * The SndFile takes ownership of the stream and drops it when closed.
+     *
+     * # Arguments
+     * * `stream` - The stream containing the music
+     * * `info` - The format, sample rate and channels of the music, only
+     *   used if the stream is empty
+     *
+     * Return Ok() containing the SndFile on success, the error otherwise.
+     */
+    pub fn from_read_write<RW>(stream : RW, info : SndInfo) -> SndFileResult<SndFile<mode::ReadWrite>>
+        where RW : Read + Write + Seek + 'static {
+        open_virtual(Box::new(ReadWriter(stream)), info)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{self, Cursor, Read, Seek, SeekFrom, Write};
+
+    use super::*;
+    use formats::{Format, MajorFormat, SubtypeFormat};
+    use {ErrorKind, SndFile, SndInfo};
+
+    struct PanickingReader;
+
+    impl Read for PanickingReader {
+        fn read(&mut self, _ : &mut [u8]) -> io::Result<usize> {
+            panic!("read exploded")
+        }
+    }
+
+    impl Seek for PanickingReader {
+        fn seek(&mut self, _ : SeekFrom) -> io::Result<u64> {
+            panic!("seek exploded")
+        }
+    }
+
+    /// A writer which can seek but fails every write.
+    struct FailingWriter(Cursor<Vec<u8>>);
+
+    impl Write for FailingWriter {
+        fn write(&mut self, _ : &[u8]) -> io::Result<usize> {
+            Err(io::Error::new(io::ErrorKind::BrokenPipe, "write failed"))
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl Seek for FailingWriter {
+        fn seek(&mut self, pos : SeekFrom) -> io::Result<u64> {
+            self.0.seek(pos)
+        }
+    }
+
+    fn virtual_stream(stream : Box<dyn Stream>) -> VirtualStream {
+        VirtualStream { stream, error : None }
+    }
+
+    #[test]
+    fn call_records_errors_once() {
+        let mut vs = virtual_stream(Box::new(Reader(Cursor::new(vec![1, 2, 3]))));
+        assert_eq!(vs.call(-1, |stream| stream.read(&mut [0; 2]).map(|n| n as i64)), 2);
+        assert!(vs.take_error().is_none());
+
+        assert_eq!(vs.call(-1, |stream| stream.write(&[0]).map(|n| n as i64)), -1);
+        assert_eq!(vs.take_error().unwrap().to_string(), read_only().to_string());
+        assert!(vs.take_error().is_none());
+    }
+
+    #[test]
+    fn call_catches_panics() {
+        let mut vs = virtual_stream(Box::new(Reader(PanickingReader)));
+        assert_eq!(vs.call(0, |stream| stream.read(&mut [0; 4]).map(|n| n as i64)), 0);
+        let err = vs.take_error().unwrap();
+        assert_eq!(err.kind(), io::ErrorKind::Other);
+        assert_eq!(err.to_string(), "panic in virtual I/O callback: read exploded");
+    }
+
+    #[test]
+    fn panic_payloads() {
+        let message = |payload : Box<dyn Any + Send>| panic_to_error(payload).to_string();
+        assert_eq!(message(Box::new("static")), "panic in virtual I/O callback: static");
+        assert_eq!(message(Box::new("owned".to_string())), "panic in virtual I/O callback: owned");
+        assert_eq!(message(Box::new(42)), "panic in virtual I/O callback: unknown panic");
+    }
+
+    #[test]
+    fn open_reports_panicking_stream() {
+        let err = SndFile::from_reader(PanickingReader).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Io(io::ErrorKind::Other));
+        assert!(err.message().starts_with("panic in virtual I/O callback"));
+        assert_eq!(err.operation(), Some("open"));
+    }
+
+    #[test]
+    fn write_reports_stream_error() {
+        let mut info = SndInfo { samplerate : 8000, channels : 1, ..SndInfo::default() };
+        info.set_format(Format::new(MajorFormat::Wav, SubtypeFormat::Pcm16));
+        let result = SndFile::from_writer(FailingWriter(Cursor::new(Vec::new())), info)
+            .and_then(|mut file| {
+                file.write_f32(&[0.0; 64])?;
+                file.close()
+            });
+        assert_eq!(result.unwrap_err().kind(), ErrorKind::Io(io::ErrorKind::BrokenPipe));
+    }
+}